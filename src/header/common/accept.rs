@@ -1,6 +1,6 @@
 use mime::{self, Mime};
 
-use header::{QualityItem, qitem};
+use header::{MediaRange, Quality, QualityItem, q, qitem};
 
 header! {
     /// `Accept` header, defined in [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.3.2)
@@ -112,6 +112,58 @@ header! {
                     q(500)),
             ])));
 
+        #[test]
+        fn test_quality_zero_overrides_wildcard() {
+            let raw: Raw = "*/*;q=0.5, image/png;q=0".into();
+            let accept = Accept::parse_header(&raw).unwrap();
+            assert_eq!(accept.quality(&IMAGE_PNG), q(0));
+            assert!(!accept.is_acceptable(&IMAGE_PNG));
+            assert!(accept.is_acceptable(&IMAGE_GIF));
+            assert_eq!(accept.quality(&TEXT_HTML), q(500));
+
+            // regardless of order
+            let raw: Raw = "image/png;q=0, image/*;q=0.8, */*;q=0.5".into();
+            let accept = Accept::parse_header(&raw).unwrap();
+            assert!(!accept.is_acceptable(&IMAGE_PNG));
+            assert_eq!(accept.quality(&IMAGE_JPEG), q(800));
+            assert_eq!(accept.quality(&TEXT_PLAIN), q(500));
+        }
+
+        #[test]
+        fn test_prefers_html() {
+            let raw: Raw = "text/html,application/xhtml+xml,application/xml;q=0.9,\
+                            image/avif,image/webp,*/*;q=0.8".into();
+            assert!(Accept::parse_header(&raw).unwrap().prefers_html());
+            let raw: Raw = "text/html;charset=utf-8, application/json".into();
+            assert!(Accept::parse_header(&raw).unwrap().prefers_html());
+
+            assert!(!Accept::json().prefers_html());
+            assert!(!Accept::star().prefers_html());
+            assert!(!Accept::text().prefers_html());
+            let raw: Raw = "application/json, text/html;q=0.5".into();
+            assert!(!Accept::parse_header(&raw).unwrap().prefers_html());
+            let raw: Raw = "text/html;q=0, */*".into();
+            assert!(!Accept::parse_header(&raw).unwrap().prefers_html());
+        }
+
+        #[test]
+        fn test_quality_precedence() {
+            // RFC 7231, Section 5.3.2
+            let raw: Raw = "text/*;q=0.3, text/html;q=0.7, text/html;level=1, \
+                            text/html;level=2;q=0.4, */*;q=0.5".into();
+            let accept = Accept::parse_header(&raw).unwrap();
+            assert_eq!(accept.quality(&"text/html;level=1".parse().unwrap()), q(1000));
+            assert_eq!(accept.quality(&TEXT_HTML), q(700));
+            assert_eq!(accept.quality(&TEXT_PLAIN), q(300));
+            assert_eq!(accept.quality(&IMAGE_JPEG), q(500));
+            assert_eq!(accept.quality(&"text/html;level=2".parse().unwrap()), q(400));
+            assert_eq!(accept.quality(&"text/html;level=3".parse().unwrap()), q(700));
+
+            let raw: Raw = "text/html".into();
+            let accept = Accept::parse_header(&raw).unwrap();
+            assert!(!accept.is_acceptable(&TEXT_PLAIN));
+        }
+
         #[test]
         fn test_fuzzing1() {
             let raw: Raw = "chunk#;e".into();
@@ -121,6 +173,9 @@ header! {
     }
 }
 
+__hyper__list!(Accept => QualityItem<Mime>);
+__hyper__from_str!(Accept);
+
 impl Accept {
     /// A constructor to easily create `Accept: */*`.
     pub fn star() -> Accept {
@@ -141,6 +196,85 @@ impl Accept {
     pub fn image() -> Accept {
         Accept(vec![qitem(mime::IMAGE_STAR)])
     }
+
+    /// Get the quality with which a media type is acceptable.
+    ///
+    /// Where several media ranges match, the most specific takes
+    /// precedence, so that an explicit `q=0` rejects a media type despite a
+    /// broader range, as in `*/*;q=0.5, image/png;q=0`. A media type
+    /// matching no range is not acceptable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{Accept, Header, Raw, q};
+    ///
+    /// let raw: Raw = "*/*;q=0.5, image/png;q=0".into();
+    /// let accept = Accept::parse_header(&raw).unwrap();
+    /// assert_eq!(accept.quality(&"image/gif".parse().unwrap()), q(500));
+    /// assert!(!accept.is_acceptable(&"image/png".parse().unwrap()));
+    /// ```
+    pub fn quality(&self, mime: &Mime) -> Quality {
+        let mut best = None;
+        for item in self.iter() {
+            let range = match MediaRange::new(item.item.clone()) {
+                Some(range) => range,
+                None => continue,
+            };
+            if !range.matches(mime) {
+                continue;
+            }
+            let rank = specificity(range.as_mime());
+            match best {
+                Some((best_rank, _)) if best_rank >= rank => (),
+                _ => best = Some((rank, item.quality)),
+            }
+        }
+        best.map_or_else(|| q(0), |(_, quality)| quality)
+    }
+
+    /// Check whether a media type is acceptable, i.e. has a non-zero quality.
+    pub fn is_acceptable(&self, mime: &Mime) -> bool {
+        self.quality(mime) > q(0)
+    }
+
+    /// Check whether an HTML response should be preferred over JSON, as for a
+    /// request made by a browser rather than an API client.
+    ///
+    /// This is the case when `text/html` is listed explicitly, rather than
+    /// only matched by a wildcard range such as `*/*`, and its quality is
+    /// non-zero and at least that of `application/json`. So a browser's
+    /// `text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8`
+    /// prefers HTML, while `application/json`, `*/*` or
+    /// `application/json, text/html;q=0.5` do not.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{Accept, Header, Raw};
+    ///
+    /// let raw: Raw = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8".into();
+    /// assert!(Accept::parse_header(&raw).unwrap().prefers_html());
+    /// assert!(!Accept::json().prefers_html());
+    /// ```
+    pub fn prefers_html(&self) -> bool {
+        let json = self.quality(&mime::APPLICATION_JSON);
+        self.iter()
+            .filter(|item| item.item.type_() == mime::TEXT && item.item.subtype() == mime::HTML)
+            .any(|item| item.quality > q(0) && item.quality >= json)
+    }
+}
+
+// Rank a media range by wildcards, then number of parameters.
+fn specificity(range: &Mime) -> (u8, usize) {
+    let wildcards = if range.type_() == mime::STAR {
+        0
+    } else if range.subtype() == mime::STAR {
+        1
+    } else {
+        2
+    };
+    (wildcards, range.params().count())
 }
 
 
@@ -1,4 +1,7 @@
-use header::{Charset, QualityItem};
+use mime;
+use unicase;
+
+use header::{Charset, ContentType, Quality, QualityItem, q};
 
 header! {
     /// `Accept-Charset` header, defined in
@@ -53,6 +56,86 @@ header! {
     test_accept_charset {
         /// Testcase from RFC
         test_header!(test1, vec![b"iso-8859-5, unicode-1-1;q=0.8"]);
+
+        #[test]
+        fn test_accepts_content_type() {
+            let r: Raw = "utf-8, iso-8859-1;q=0".into();
+            let a = HeaderField::parse_header(&r).unwrap();
+            assert!(a.accepts_content_type(&ContentType::json()));
+            assert!(a.accepts_content_type(&ContentType("text/plain; charset=UTF-8".parse().unwrap())));
+            assert!(!a.accepts_content_type(&ContentType("text/html; charset=iso-8859-1".parse().unwrap())));
+            assert!(!a.accepts_content_type(&ContentType("text/html; charset=shift-jis".parse().unwrap())));
+            // without a charset there is nothing to check
+            assert!(a.accepts_content_type(&ContentType::png()));
+        }
+
+        #[test]
+        fn test_quality_wildcard() {
+            let r: Raw = "iso-8859-5, *;q=0.5, utf-8;q=0".into();
+            let a = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(a.quality(&Charset::Iso_8859_5), q(1000));
+            assert_eq!(a.quality(&Charset::Us_Ascii), q(500));
+            assert_eq!(a.quality(&Charset::Ext("utf-8".to_owned())), q(0));
+            assert!(!a.is_acceptable(&Charset::Ext("UTF-8".to_owned())));
+            assert!(a.is_acceptable(&Charset::Big5));
+        }
+    }
+}
+
+__hyper__list!(AcceptCharset => QualityItem<Charset>);
+__hyper__from_str!(AcceptCharset);
+
+impl AcceptCharset {
+    /// Get the quality given to a charset, `q=0` meaning not acceptable.
+    ///
+    /// A charset listed explicitly gets its own weight, otherwise that of
+    /// `*`. A charset matching neither is not acceptable.
+    pub fn quality(&self, charset: &Charset) -> Quality {
+        let name = charset.to_string();
+        let mut star = None;
+        for item in self.iter() {
+            let item_name = item.item.to_string();
+            if unicase::eq_ascii(&*item_name, &*name) {
+                return item.quality;
+            }
+            if item_name == "*" && star.is_none() {
+                star = Some(item.quality);
+            }
+        }
+        star.unwrap_or_else(|| q(0))
+    }
+
+    /// Check whether a charset is acceptable, i.e. has a non-zero quality.
+    pub fn is_acceptable(&self, charset: &Charset) -> bool {
+        self.quality(charset) > q(0)
+    }
+
+    /// Check whether the charset of a response's `Content-Type` is
+    /// acceptable. A `Content-Type` without a charset is always acceptable.
+    ///
+    /// If this returns `false`, the server should respond with
+    /// `406 Not Acceptable` (or disregard the header and send the
+    /// response anyway).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{AcceptCharset, Charset, ContentType, QualityItem, q, qitem};
+    ///
+    /// let accept = AcceptCharset(vec![
+    ///     qitem(Charset::Ext("UTF-8".to_owned())),
+    ///     QualityItem::new(Charset::Iso_8859_1, q(0)),
+    /// ]);
+    /// assert!(accept.accepts_content_type(&ContentType::html()));
+    /// ```
+    pub fn accepts_content_type(&self, content_type: &ContentType) -> bool {
+        match content_type.get_param(mime::CHARSET) {
+            Some(charset) => match charset.as_str().parse() {
+                Ok(charset) => self.is_acceptable(&charset),
+                Err(_) => false,
+            },
+            None => true,
+        }
     }
 }
 
@@ -1,4 +1,5 @@
-use header::{Encoding, QualityItem};
+use header::{Encoding, Quality, QualityItem, q};
+use unicase;
 
 header! {
     /// `Accept-Encoding` header, defined in
@@ -30,7 +31,7 @@ header! {
     ///
     /// let mut headers = Headers::new();
     /// headers.set(
-    ///     AcceptEncoding(vec![qitem(Encoding::Chunked)])
+    ///     AcceptEncoding(vec![qitem(Encoding::Gzip)])
     /// );
     /// ```
     /// ```
@@ -39,7 +40,7 @@ header! {
     /// let mut headers = Headers::new();
     /// headers.set(
     ///     AcceptEncoding(vec![
-    ///         qitem(Encoding::Chunked),
+    ///         qitem(Encoding::Brotli),
     ///         qitem(Encoding::Gzip),
     ///         qitem(Encoding::Deflate),
     ///     ])
@@ -51,12 +52,16 @@ header! {
     /// let mut headers = Headers::new();
     /// headers.set(
     ///     AcceptEncoding(vec![
-    ///         qitem(Encoding::Chunked),
+    ///         qitem(Encoding::Brotli),
     ///         QualityItem::new(Encoding::Gzip, q(600)),
     ///         QualityItem::new(Encoding::EncodingExt("*".to_owned()), q(0)),
     ///     ])
     /// );
     /// ```
+    ///
+    /// Codings which are not valid content-codings, such as `chunked`, are
+    /// still parsed, keeping the other preferences. Check them with
+    /// `is_valid`.
     (AcceptEncoding, "Accept-Encoding") => (QualityItem<Encoding>)*
 
     test_accept_encoding {
@@ -68,6 +73,163 @@ header! {
         test_header!(test4, vec![b"compress;q=0.5, gzip"]);
         // Note: Removed quality 1 from gzip
         test_header!(test5, vec![b"gzip, identity; q=0.5, *;q=0"]);
+
+        test_header!(
+            test_dictionary,
+            vec![b"dcb, dcz, gzip"],
+            Some(HeaderField(vec![
+                qitem(Encoding::DictionaryBrotli(vec![])),
+                qitem(Encoding::DictionaryZstd(vec![])),
+                qitem(Encoding::Gzip),
+            ])));
+        test_header!(
+            test_dictionary_params,
+            vec![b"dcb;x=1; q=0.5, dcz"],
+            Some(HeaderField(vec![
+                QualityItem::new(
+                    Encoding::DictionaryBrotli(vec![("x".to_owned(), "1".to_owned())]),
+                    q(500)),
+                qitem(Encoding::DictionaryZstd(vec![])),
+            ])));
+        // Unknown codings and parameters are kept
+        test_header!(test_ext_params, vec![b"x-foo;level=3; q=0.5, dcz"]);
+
+        #[test]
+        fn test_is_valid() {
+            let r: Raw = "gzip, br;q=0.5".into();
+            assert!(HeaderField::parse_header(&r).unwrap().is_valid());
+            let r: Raw = "gzip, chunked".into();
+            let h = HeaderField::parse_header(&r).unwrap();
+            assert!(!h.is_valid());
+            assert_eq!(h.preferred(&[Encoding::Gzip]), Some(Encoding::Gzip));
+        }
+
+        #[test]
+        fn test_ext_params_kept() {
+            let r: Raw = "x-foo;level=3;q=0.5".into();
+            let h = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(h, HeaderField(vec![
+                QualityItem::new(Encoding::EncodingExt("x-foo;level=3".to_owned()), q(500)),
+            ]));
+        }
+
+        #[test]
+        fn test_identity_rejected_gzip_available() {
+            let r: Raw = "gzip, identity;q=0".into();
+            let h = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(h.quality(&Encoding::Identity), q(0));
+            assert_eq!(h.preferred(&[Encoding::Gzip]), Some(Encoding::Gzip));
+            assert_eq!(h.preferred(&[Encoding::Brotli]), None);
+            assert_eq!(h.preferred(&[]), None);
+        }
+
+        #[test]
+        fn test_total_rejection() {
+            let r: Raw = "identity;q=0, *;q=0".into();
+            let h = HeaderField::parse_header(&r).unwrap();
+            assert!(!h.is_acceptable(&Encoding::Identity));
+            assert!(!h.is_acceptable(&Encoding::Gzip));
+            assert_eq!(h.preferred(&[]), None);
+            assert_eq!(h.preferred(&[Encoding::Gzip, Encoding::Brotli]), None);
+
+            // `*;q=0` alone also excludes identity
+            let r: Raw = "*;q=0".into();
+            let h = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(h.preferred(&[]), None);
+
+            // unless identity is listed more specifically
+            let r: Raw = "identity, *;q=0".into();
+            let h = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(h.preferred(&[Encoding::Gzip]), Some(Encoding::Identity));
+        }
+
+        #[test]
+        fn test_preferred() {
+            let r: Raw = "deflate;q=0.5, GZIP;q=0.8, *;q=0.1".into();
+            let h = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(h.quality(&Encoding::Gzip), q(800));
+            assert_eq!(h.quality(&Encoding::Brotli), q(100));
+            assert_eq!(h.preferred(&[Encoding::Brotli, Encoding::Deflate, Encoding::Gzip]),
+                       Some(Encoding::Gzip));
+            // identity is acceptable by default
+            assert_eq!(h.preferred(&[]), Some(Encoding::Identity));
+
+            // an empty value accepts only identity
+            let h = HeaderField(vec![]);
+            assert_eq!(h.preferred(&[Encoding::Gzip]), Some(Encoding::Identity));
+        }
+    }
+}
+
+__hyper__list!(AcceptEncoding => QualityItem<Encoding>);
+__hyper__from_str!(AcceptEncoding);
+
+impl AcceptEncoding {
+    /// Get the quality given to a content-coding, `q=0` meaning not
+    /// acceptable.
+    ///
+    /// A coding listed explicitly gets its own weight, otherwise that of
+    /// `*`. A coding matching neither is not acceptable, except `identity`,
+    /// which is acceptable unless excluded by `identity;q=0` or `*;q=0`.
+    pub fn quality(&self, encoding: &Encoding) -> Quality {
+        let name = encoding.to_string();
+        let mut star = None;
+        for item in self.iter() {
+            let item_name = item.item.to_string();
+            if unicase::eq_ascii(&*item_name, &*name) {
+                return item.quality;
+            }
+            if item_name == "*" && star.is_none() {
+                star = Some(item.quality);
+            }
+        }
+        match star {
+            Some(quality) => quality,
+            None if *encoding == Encoding::Identity => q(1000),
+            None => q(0),
+        }
+    }
+
+    /// Check whether a content-coding is acceptable, i.e. has a non-zero
+    /// quality.
+    pub fn is_acceptable(&self, encoding: &Encoding) -> bool {
+        self.quality(encoding) > q(0)
+    }
+
+    /// Select the content-coding to respond with, from those `available` in
+    /// the server's order of preference, falling back to `identity`.
+    ///
+    /// The acceptable coding of highest quality is chosen, ties going to the
+    /// earlier in `available`. If no coding is acceptable, including
+    /// `identity`, as for `identity;q=0, *;q=0`, this returns `None` and the
+    /// server should respond with `406 Not Acceptable`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{AcceptEncoding, Encoding, QualityItem, q, qitem};
+    ///
+    /// let accept = AcceptEncoding(vec![
+    ///     qitem(Encoding::Gzip),
+    ///     QualityItem::new(Encoding::Identity, q(0)),
+    /// ]);
+    /// assert_eq!(accept.preferred(&[Encoding::Brotli, Encoding::Gzip]), Some(Encoding::Gzip));
+    /// assert_eq!(accept.preferred(&[Encoding::Brotli]), None);
+    /// ```
+    pub fn preferred(&self, available: &[Encoding]) -> Option<Encoding> {
+        let mut best: Option<(&Encoding, Quality)> = None;
+        for encoding in available.iter().chain(Some(&Encoding::Identity)) {
+            let quality = self.quality(encoding);
+            if quality > q(0) && best.map_or(true, |(_, best)| quality > best) {
+                best = Some((encoding, quality));
+            }
+        }
+        best.map(|(encoding, _)| encoding.clone())
+    }
+
+    /// Check that every coding is a valid content-coding.
+    pub fn is_valid(&self) -> bool {
+        self.iter().all(|item| item.item.is_valid_content_coding())
     }
 }
 
@@ -1,5 +1,5 @@
 use language_tags::LanguageTag;
-use header::QualityItem;
+use header::{Quality, QualityItem, q};
 
 header! {
     /// `Accept-Language` header, defined in
@@ -65,6 +65,118 @@ header! {
                 QualityItem::new("en".parse().unwrap(), q(500)),
                 qitem("fr".parse().unwrap()),
         ])));
+
+        #[test]
+        fn test_ranges_by_quality() {
+            let a: Raw = "en;q=0.8, fr".into();
+            let a = HeaderField::parse_header(&a).unwrap();
+            let ranges = a.ranges_by_quality().into_iter()
+                .map(|(tag, q)| (tag.to_string(), q))
+                .collect::<Vec<_>>();
+            assert_eq!(ranges, vec![("fr".to_owned(), q(1000)), ("en".to_owned(), q(800))]);
+
+            // equal qualities keep the order in which they were sent
+            let a: Raw = "da;q=0.5, en-gb, i-klingon;q=0, en, de;q=0.5".into();
+            let a = HeaderField::parse_header(&a).unwrap();
+            let tags = a.ranges_by_quality().into_iter()
+                .map(|(tag, _)| tag.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(tags, vec!["en-GB", "en", "da", "de", "i-klingon"]);
+        }
+
+        #[test]
+        fn test_fallback_chain() {
+            fn chain(s: &str) -> Vec<String> {
+                let r: Raw = s.into();
+                HeaderField::parse_header(&r).unwrap()
+                    .fallback_chain().iter()
+                    .map(|tag| tag.to_string())
+                    .collect()
+            }
+
+            assert_eq!(chain("en-US;q=0.9, de"), vec!["de", "en-US", "en"]);
+            assert_eq!(chain("en-US-x-foo;q=0.8, de-CH"),
+                       vec!["de-CH", "de", "en-US-x-foo", "en-US", "en"]);
+            // a tag listed more than once takes its highest quality
+            assert_eq!(chain("en-GB, fr;q=0.5, en;q=0.4"), vec!["en-GB", "en", "fr"]);
+            assert_eq!(chain("en-GB;q=0.4, en"), vec!["en", "en-GB"]);
+            assert_eq!(chain("fr;q=0, zh-Hant-TW"), vec!["zh-Hant-TW", "zh-Hant", "zh"]);
+            assert_eq!(chain("en-US, en;q=0"), vec!["en-US"]);
+        }
+    }
+}
+
+__hyper__list!(AcceptLanguage => QualityItem<LanguageTag>);
+__hyper__from_str!(AcceptLanguage);
+
+impl AcceptLanguage {
+    /// Returns the language ranges sorted by descending quality, the order
+    /// in which a server should try them.
+    ///
+    /// A range sent without a weight has the default quality of 1. Ranges of
+    /// equal quality keep the order in which they appear in the header.
+    pub fn ranges_by_quality(&self) -> Vec<(&LanguageTag, Quality)> {
+        let mut ranges = self.0.iter()
+            .map(|item| (&item.item, item.quality))
+            .collect::<Vec<_>>();
+        ranges.sort_by(|a, b| b.1.cmp(&a.1));
+        ranges
+    }
+
+    /// Returns the language tags to try in turn when looking up a
+    /// localization, with each range followed by its truncations.
+    ///
+    /// Subtags are removed from the end of each range, along with any
+    /// singleton left trailing, as for
+    /// [RFC4647 lookup](https://tools.ietf.org/html/rfc4647#section-3.4), so
+    /// that `en-US-x-foo` also yields `en-US` and then `en`. Truncations
+    /// take the quality of their range. Tags are sorted by descending
+    /// quality, then by descending number of subtags, and otherwise keep
+    /// the order in which they were sent. Each tag appears once, with its
+    /// highest quality, while tags listed with a quality of 0 are excluded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::AcceptLanguage;
+    ///
+    /// let accept: AcceptLanguage = "en-US;q=0.9, de".parse().unwrap();
+    /// let chain = accept.fallback_chain().iter()
+    ///     .map(|tag| tag.to_string())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(chain, vec!["de", "en-US", "en"]);
+    /// ```
+    pub fn fallback_chain(&self) -> Vec<LanguageTag> {
+        let excluded = self.0.iter()
+            .filter(|item| item.quality == q(0))
+            .map(|item| item.item.to_string())
+            .collect::<Vec<_>>();
+        let mut chain: Vec<(LanguageTag, Quality, usize)> = Vec::new();
+        for item in self.0.iter().filter(|item| item.quality > q(0)) {
+            let tag = item.item.to_string();
+            let mut subtags = tag.split('-').collect::<Vec<_>>();
+            while !subtags.is_empty() {
+                let truncated = subtags.join("-");
+                let existing = chain.iter()
+                    .position(|&(ref t, _, _)| t.to_string().eq_ignore_ascii_case(&truncated));
+                match existing {
+                    Some(i) => if chain[i].1 < item.quality {
+                        chain[i].1 = item.quality;
+                    },
+                    None => if !excluded.iter().any(|e| e.eq_ignore_ascii_case(&truncated)) {
+                        if let Ok(tag) = truncated.parse() {
+                            chain.push((tag, item.quality, subtags.len()));
+                        }
+                    },
+                }
+                subtags.pop();
+                while subtags.last().map_or(false, |s| s.len() == 1) {
+                    subtags.pop();
+                }
+            }
+        }
+        chain.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)));
+        chain.into_iter().map(|(tag, _, _)| tag).collect()
     }
 }
 
@@ -0,0 +1,108 @@
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+
+use header::{ContentType, MediaRange};
+
+header! {
+    /// `Accept-Patch` header, defined in
+    /// [RFC5789](https://tools.ietf.org/html/rfc5789#section-3.1)
+    ///
+    /// The `Accept-Patch` header field is used by a server to advertise the
+    /// patch document formats it accepts in a PATCH request.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Accept-Patch = 1#media-type
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `application/example, text/example`
+    /// * `application/json-patch+json, application/merge-patch+json`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, AcceptPatch, ContentType};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(AcceptPatch(vec![
+    ///     "application/json-patch+json".parse().unwrap(),
+    /// ]));
+    ///
+    /// let content_type = ContentType("application/json-patch+json".parse().unwrap());
+    /// let accept_patch = headers.get::<AcceptPatch>().unwrap();
+    /// assert!(accept_patch.check(Some(&content_type)).is_ok());
+    /// assert!(accept_patch.check(Some(&ContentType::json())).is_err());
+    /// ```
+    (AcceptPatch, "Accept-Patch") => (MediaRange)+
+
+    test_accept_patch {
+        test_header!(test1, vec![b"application/example, text/example"]);
+        test_header!(test2, vec![b"text/example;charset=utf-8"]);
+
+        #[test]
+        fn test_check() {
+            let raw: Raw = "application/json-patch+json, application/merge-patch+json".into();
+            let accept_patch = HeaderField::parse_header(&raw).unwrap();
+            let json_patch = ContentType("application/json-patch+json".parse().unwrap());
+            assert!(accept_patch.accepts(&json_patch));
+            assert_eq!(accept_patch.check(Some(&json_patch)), Ok(()));
+
+            assert!(!accept_patch.accepts(&ContentType::json()));
+            assert_eq!(accept_patch.check(Some(&ContentType::json())),
+                       Err(UnsupportedMediaType));
+            assert_eq!(accept_patch.check(None), Err(UnsupportedMediaType));
+        }
+    }
+}
+
+__hyper__list!(AcceptPatch => MediaRange);
+__hyper__from_str!(AcceptPatch);
+
+impl AcceptPatch {
+    /// Check whether a request `Content-Type` is one of the accepted patch
+    /// document formats.
+    pub fn accepts(&self, content_type: &ContentType) -> bool {
+        accepts(&self.0, content_type)
+    }
+
+    /// Check the `Content-Type` of a PATCH request, returning
+    /// `UnsupportedMediaType` if it is missing or not accepted.
+    pub fn check(&self, content_type: Option<&ContentType>)
+        -> Result<(), UnsupportedMediaType>
+    {
+        check(&self.0, content_type)
+    }
+}
+
+/// The request body media type is not accepted, and the request should be
+/// rejected with `415 Unsupported Media Type`.
+///
+/// Returned by `AcceptPatch::check` and `AcceptPost::check`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UnsupportedMediaType;
+
+impl Display for UnsupportedMediaType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("request media type is not accepted")
+    }
+}
+
+impl StdError for UnsupportedMediaType {}
+
+pub fn accepts(ranges: &[MediaRange], content_type: &ContentType) -> bool {
+    ranges.iter().any(|range| range.matches(content_type))
+}
+
+pub fn check(ranges: &[MediaRange], content_type: Option<&ContentType>)
+    -> Result<(), UnsupportedMediaType>
+{
+    match content_type {
+        Some(content_type) if accepts(ranges, content_type) => Ok(()),
+        _ => Err(UnsupportedMediaType),
+    }
+}
+
+bench_header!(bench, AcceptPatch, { vec![b"application/example, text/example".to_vec()] });
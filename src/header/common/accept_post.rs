@@ -0,0 +1,78 @@
+use header::{ContentType, MediaRange};
+use super::accept_patch::{self, UnsupportedMediaType};
+
+header! {
+    /// `Accept-Post` header, defined in
+    /// [Linked Data Platform 1.0](https://www.w3.org/TR/ldp/#header-accept-post)
+    ///
+    /// The `Accept-Post` header field is used by a server to advertise the
+    /// media types it accepts in a POST request.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Accept-Post = #media-range
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `text/turtle, application/ld+json`
+    /// * `image/*`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, AcceptPost, ContentType};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(AcceptPost(vec![
+    ///     "image/*".parse().unwrap(),
+    /// ]));
+    ///
+    /// let accept_post = headers.get::<AcceptPost>().unwrap();
+    /// assert!(accept_post.check(Some(&ContentType::png())).is_ok());
+    /// assert!(accept_post.check(Some(&ContentType::json())).is_err());
+    /// ```
+    (AcceptPost, "Accept-Post") => (MediaRange)*
+
+    test_accept_post {
+        test_header!(test1, vec![b"text/turtle, application/ld+json"]);
+        test_header!(test2, vec![b"image/*"]);
+        test_header!(test3, vec![b""], Some(HeaderField(vec![])));
+
+        #[test]
+        fn test_check() {
+            let raw: Raw = "text/turtle, image/*".into();
+            let accept_post = HeaderField::parse_header(&raw).unwrap();
+            assert!(accept_post.accepts(&ContentType::png()));
+            assert!(accept_post.accepts(&ContentType("text/turtle".parse().unwrap())));
+            assert_eq!(accept_post.check(Some(&ContentType::jpeg())), Ok(()));
+
+            assert!(!accept_post.accepts(&ContentType::json()));
+            assert_eq!(accept_post.check(Some(&ContentType::json())),
+                       Err(UnsupportedMediaType));
+            assert_eq!(accept_post.check(None), Err(UnsupportedMediaType));
+        }
+    }
+}
+
+__hyper__list!(AcceptPost => MediaRange);
+__hyper__from_str!(AcceptPost);
+
+impl AcceptPost {
+    /// Check whether a request `Content-Type` falls within one of the
+    /// accepted media ranges.
+    pub fn accepts(&self, content_type: &ContentType) -> bool {
+        accept_patch::accepts(&self.0, content_type)
+    }
+
+    /// Check the `Content-Type` of a POST request, returning
+    /// `UnsupportedMediaType` if it is missing or not accepted.
+    pub fn check(&self, content_type: Option<&ContentType>)
+        -> Result<(), UnsupportedMediaType>
+    {
+        accept_patch::check(&self.0, content_type)
+    }
+}
+
+bench_header!(bench, AcceptPost, { vec![b"text/turtle, application/ld+json".to_vec()] });
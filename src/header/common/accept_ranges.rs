@@ -1,5 +1,6 @@
 use std::fmt::{self, Display};
 use std::str::FromStr;
+use unicase;
 
 header! {
     /// `Accept-Ranges` header, defined in
@@ -55,6 +56,34 @@ header! {
         test_header!(test2, vec![b"none"]);
         test_header!(test3, vec![b"unknown-unit"]);
         test_header!(test4, vec![b"bytes, unknown-unit"]);
+
+        #[test]
+        fn test_supports() {
+            let r: Raw = "bytes, Pages".into();
+            let ranges = HeaderField::parse_header(&r).unwrap();
+            assert!(ranges.supports(&RangeUnit::Bytes));
+            assert!(ranges.supports(&RangeUnit::Unregistered("pages".to_owned())));
+            assert!(!ranges.supports(&RangeUnit::Unregistered("lines".to_owned())));
+            assert!(!ranges.supports(&RangeUnit::None));
+
+            let r: Raw = "none".into();
+            let ranges = HeaderField::parse_header(&r).unwrap();
+            assert!(!ranges.supports(&RangeUnit::Bytes));
+            assert!(!ranges.supports(&RangeUnit::None));
+        }
+    }
+}
+
+__hyper__list!(AcceptRanges => RangeUnit);
+__hyper__from_str!(AcceptRanges);
+
+impl AcceptRanges {
+    /// Returns true if the given range unit is listed as supported.
+    ///
+    /// Units are compared case-insensitively, and `none` is never supported.
+    pub fn supports(&self, unit: &RangeUnit) -> bool {
+        *unit != RangeUnit::None &&
+            self.iter().any(|u| unicase::eq_ascii(u.as_str(), unit.as_str()))
     }
 }
 
@@ -71,7 +100,7 @@ header! {
 /// bytes-unit       = "bytes"
 /// other-range-unit = token
 /// ```
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum RangeUnit {
     /// Indicating byte-range requests are supported.
     Bytes,
@@ -94,14 +123,20 @@ impl FromStr for RangeUnit {
     }
 }
 
-impl Display for RangeUnit {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl RangeUnit {
+    fn as_str(&self) -> &str {
         match *self {
-            RangeUnit::Bytes => f.write_str("bytes"),
-            RangeUnit::None => f.write_str("none"),
-            RangeUnit::Unregistered(ref x) => f.write_str(x),
+            RangeUnit::Bytes => "bytes",
+            RangeUnit::None => "none",
+            RangeUnit::Unregistered(ref x) => x,
         }
     }
 }
 
+impl Display for RangeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 standard_header!(AcceptRanges, ACCEPT_RANGES);
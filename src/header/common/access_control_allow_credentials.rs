@@ -72,6 +72,8 @@ impl Header for AccessControlAllowCredentials {
     }
 }
 
+__hyper__from_str!(AccessControlAllowCredentials);
+
 impl Display for AccessControlAllowCredentials {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         f.write_str("true")
@@ -60,4 +60,7 @@ header! {
     }
 }
 
+__hyper__list!(AccessControlAllowHeaders => Ascii<String>);
+__hyper__from_str!(AccessControlAllowHeaders);
+
 standard_header!(AccessControlAllowHeaders, ACCESS_CONTROL_ALLOW_HEADERS);
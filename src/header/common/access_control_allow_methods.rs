@@ -50,4 +50,7 @@ header! {
     }
 }
 
+__hyper__list!(AccessControlAllowMethods => Method);
+__hyper__from_str!(AccessControlAllowMethods);
+
 standard_header!(AccessControlAllowMethods, ACCESS_CONTROL_ALLOW_METHODS);
@@ -80,6 +80,8 @@ impl Header for AccessControlAllowOrigin {
     }
 }
 
+__hyper__from_str!(AccessControlAllowOrigin);
+
 impl Display for AccessControlAllowOrigin {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         match *self {
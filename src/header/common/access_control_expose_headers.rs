@@ -62,4 +62,7 @@ header! {
     }
 }
 
+__hyper__list!(AccessControlExposeHeaders => Ascii<String>);
+__hyper__from_str!(AccessControlExposeHeaders);
+
 standard_header!(AccessControlExposeHeaders, ACCESS_CONTROL_EXPOSE_HEADERS);
@@ -30,4 +30,6 @@ header! {
     }
 }
 
+__hyper__from_str!(AccessControlMaxAge);
+
 standard_header!(AccessControlMaxAge, ACCESS_CONTROL_MAX_AGE);
@@ -60,4 +60,7 @@ header! {
     }
 }
 
+__hyper__list!(AccessControlRequestHeaders => Ascii<String>);
+__hyper__from_str!(AccessControlRequestHeaders);
+
 standard_header!(AccessControlRequestHeaders, ACCESS_CONTROL_REQUEST_HEADERS);
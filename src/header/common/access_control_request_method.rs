@@ -31,4 +31,6 @@ header! {
     }
 }
 
+__hyper__from_str!(AccessControlRequestMethod);
+
 standard_header!(AccessControlRequestMethod, ACCESS_CONTROL_REQUEST_METHOD);
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use header::parsing::duration_to_secs_saturating;
+
+header! {
+    /// `Age` header, defined in
+    /// [RFC7234](https://tools.ietf.org/html/rfc7234#section-5.1)
+    ///
+    /// The `Age` header field conveys the sender's estimate of the amount of
+    /// time since the response was generated or successfully validated at
+    /// the origin server.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Age = delta-seconds
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `60`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use hyperx::header::{Headers, Age};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(Age(60));
+    /// headers.set(Age::from(Duration::from_secs(60)));
+    /// ```
+    (Age, "Age") => [u32]
+
+    test_age {
+        test_header!(test1, vec![b"60"]);
+        test_header!(test2, vec![b"0"], Some(HeaderField(0)));
+        test_header!(test3, vec![b"-1"], None::<HeaderField>);
+    }
+}
+
+__hyper__from_str!(Age);
+
+impl From<Duration> for Age {
+    /// Convert with `duration_to_secs_saturating`.
+    fn from(duration: Duration) -> Age {
+        Age(duration_to_secs_saturating(duration))
+    }
+}
+
+bench_header!(bench, Age, { vec![b"60".to_vec()] });
+
+standard_header!(Age, AGE);
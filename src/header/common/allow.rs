@@ -75,6 +75,9 @@ header! {
     }
 }
 
+__hyper__list!(Allow => Method);
+__hyper__from_str!(Allow);
+
 bench_header!(bench,
     Allow, { vec![b"OPTIONS,GET,PUT,POST,DELETE,HEAD,TRACE,CONNECT,PATCH,fOObAr".to_vec()] });
 
@@ -110,6 +110,16 @@ impl<S: Scheme + Any> Header for Authorization<S> where <S as FromStr>::Err: 'st
     }
 }
 
+impl<S: Scheme + Any> FromStr for Authorization<S> where <S as FromStr>::Err: 'static {
+    type Err = ::Error;
+
+    /// Parse a single line header value with `Header::parse_header`.
+    fn from_str(s: &str) -> ::Result<Authorization<S>> {
+        let raw: ::header::Raw = s.into();
+        Authorization::parse_header(&raw)
+    }
+}
+
 impl<S: Scheme> fmt::Display for Authorization<S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let Some(scheme) = <S as Scheme>::scheme() {
@@ -1,7 +1,9 @@
 use std::fmt;
 use std::str::FromStr;
+use std::time::Duration;
 use header::{Header, RawLike};
-use header::parsing::{from_comma_delimited, fmt_comma_delimited};
+use header::parsing::{duration_to_secs_saturating, from_comma_delimited, fmt_comma_delimited,
+                      fmt_token_or_quoted, unescape};
 
 /// `Cache-Control` header, defined in [RFC7234](https://tools.ietf.org/html/rfc7234#section-5.2)
 ///
@@ -52,6 +54,13 @@ pub struct CacheControl(pub Vec<CacheDirective>);
 
 __hyper__deref!(CacheControl => Vec<CacheDirective>);
 
+impl Default for CacheControl {
+    /// An empty `CacheControl`, with no directives.
+    fn default() -> CacheControl {
+        CacheControl(Vec::new())
+    }
+}
+
 //TODO: this could just be the header! macro
 impl Header for CacheControl {
     fn header_name() -> &'static str {
@@ -75,6 +84,80 @@ impl Header for CacheControl {
     }
 }
 
+__hyper__from_str!(CacheControl);
+
+impl CacheControl {
+    /// Check that every directive may be used in the given context, returning
+    /// those which may not.
+    ///
+    /// Caches and proxies should reject or ignore such directives, for
+    /// example `public` or `s-maxage` in a request, or `only-if-cached` in a
+    /// response. Extension directives are always accepted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{CacheControl, CacheDirective, DirectiveContext};
+    ///
+    /// let cc = CacheControl(vec![CacheDirective::Public, CacheDirective::MaxAge(60)]);
+    /// assert!(cc.validate_for(DirectiveContext::Response).is_ok());
+    /// assert_eq!(cc.validate_for(DirectiveContext::Request),
+    ///            Err(vec![&CacheDirective::Public]));
+    /// ```
+    pub fn validate_for(&self, context: DirectiveContext) -> Result<(), Vec<&CacheDirective>> {
+        let invalid = self.iter()
+            .filter(|d| !d.is_valid_in(context))
+            .collect::<Vec<_>>();
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid)
+        }
+    }
+
+    /// Returns true if a cache must revalidate the response with the origin
+    /// server before using it.
+    ///
+    /// This is the case for `no-cache`, and for `must-revalidate` once the
+    /// response is stale, which from the directives alone is on receipt with
+    /// `max-age=0`. Such a response may still be stored; see
+    /// `prevents_storage`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{CacheControl, CacheDirective};
+    ///
+    /// let cc = CacheControl(vec![CacheDirective::NoCache]);
+    /// assert!(cc.requires_revalidation());
+    /// assert!(!cc.prevents_storage());
+    /// ```
+    pub fn requires_revalidation(&self) -> bool {
+        self.contains(&CacheDirective::NoCache) ||
+            (self.contains(&CacheDirective::MustRevalidate) &&
+             self.contains(&CacheDirective::MaxAge(0)))
+    }
+
+    /// Returns true if a cache must not store any part of the request or
+    /// response, as for `no-store`.
+    ///
+    /// This does not account for `must-understand`, with which a cache that
+    /// understands the response status code may ignore `no-store`.
+    pub fn prevents_storage(&self) -> bool {
+        self.contains(&CacheDirective::NoStore)
+    }
+}
+
+/// The kind of message a `CacheControl` is sent in, for
+/// `CacheControl::validate_for`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DirectiveContext {
+    /// A request message.
+    Request,
+    /// A response message.
+    Response,
+}
+
 impl fmt::Display for CacheControl {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt_comma_delimited(f, &self[..])
@@ -82,7 +165,7 @@ impl fmt::Display for CacheControl {
 }
 
 /// `CacheControl` contains a list of these directives.
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub enum CacheDirective {
     /// "no-cache"
     NoCache,
@@ -112,6 +195,22 @@ pub enum CacheDirective {
     ProxyRevalidate,
     /// "s-maxage=delta"
     SMaxAge(u32),
+    /// "immutable", defined in [RFC8246](https://tools.ietf.org/html/rfc8246)
+    Immutable,
+    /// "stale-while-revalidate=delta", defined in
+    /// [RFC5861](https://tools.ietf.org/html/rfc5861#section-3)
+    StaleWhileRevalidate(u32),
+    /// "stale-if-error=delta", defined in
+    /// [RFC5861](https://tools.ietf.org/html/rfc5861#section-4)
+    StaleIfError(u32),
+    /// "must-understand", defined in
+    /// [RFC9111](https://tools.ietf.org/html/rfc9111#section-5.2.2.3)
+    ///
+    /// A cache may only store the response if it understands the caching
+    /// requirements of its status code. It is usually sent along with
+    /// `no-store`, which a cache implementing `must-understand` ignores when
+    /// it does understand the status code.
+    MustUnderstand,
 
     /// Extension directives. Optionally include an argument.
     Extension(String, Option<String>)
@@ -135,14 +234,50 @@ impl fmt::Display for CacheDirective {
             Private => "private",
             ProxyRevalidate => "proxy-revalidate",
             SMaxAge(secs) => return write!(f, "s-maxage={}", secs),
+            Immutable => "immutable",
+            StaleWhileRevalidate(secs) => return write!(f, "stale-while-revalidate={}", secs),
+            StaleIfError(secs) => return write!(f, "stale-if-error={}", secs),
+            MustUnderstand => "must-understand",
 
             Extension(ref name, None) => &name[..],
-            Extension(ref name, Some(ref arg)) => return write!(f, "{}={}", name, arg),
+            Extension(ref name, Some(ref arg)) => {
+                try!(write!(f, "{}=", name));
+                return fmt_token_or_quoted(f, arg);
+            },
 
         }, f)
     }
 }
 
+impl CacheDirective {
+    /// Create a `max-age` directive, converting the duration with
+    /// `duration_to_secs_saturating`.
+    pub fn max_age(duration: Duration) -> CacheDirective {
+        CacheDirective::MaxAge(duration_to_secs_saturating(duration))
+    }
+
+    /// Returns true if this directive is defined for the given context.
+    pub fn is_valid_in(&self, context: DirectiveContext) -> bool {
+        use self::CacheDirective::*;
+        match *self {
+            MaxStale(_) | MinFresh(_) | OnlyIfCached => context == DirectiveContext::Request,
+            MustRevalidate | Public | Private | ProxyRevalidate | SMaxAge(_) | Immutable |
+            StaleWhileRevalidate(_) | MustUnderstand => context == DirectiveContext::Response,
+            NoCache | NoStore | NoTransform | MaxAge(_) | StaleIfError(_) |
+            Extension(..) => true,
+        }
+    }
+}
+
+// Strip the quotes of a quoted-string argument, leaving its quoted-pairs.
+fn unquote(arg: &str) -> &str {
+    if arg.len() >= 2 && arg.starts_with('"') && arg.ends_with('"') {
+        &arg[1..arg.len() - 1]
+    } else {
+        arg
+    }
+}
+
 impl FromStr for CacheDirective {
     type Err = Option<<u32 as FromStr>::Err>;
     fn from_str(s: &str) -> Result<CacheDirective, Option<<u32 as FromStr>::Err>> {
@@ -156,14 +291,18 @@ impl FromStr for CacheDirective {
             "public" => Ok(Public),
             "private" => Ok(Private),
             "proxy-revalidate" => Ok(ProxyRevalidate),
+            "immutable" => Ok(Immutable),
+            "must-understand" => Ok(MustUnderstand),
             "" => Err(None),
             _ => match s.find('=') {
-                Some(idx) if idx+1 < s.len() => match (&s[..idx], (&s[idx+1..]).trim_matches('"')) {
+                Some(idx) if idx+1 < s.len() => match (&s[..idx], unquote(&s[idx+1..])) {
                     ("max-age" , secs) => secs.parse().map(MaxAge).map_err(Some),
                     ("max-stale", secs) => secs.parse().map(MaxStale).map_err(Some),
                     ("min-fresh", secs) => secs.parse().map(MinFresh).map_err(Some),
                     ("s-maxage", secs) => secs.parse().map(SMaxAge).map_err(Some),
-                    (left, right) => Ok(Extension(left.to_owned(), Some(right.to_owned())))
+                    ("stale-while-revalidate", secs) => secs.parse().map(StaleWhileRevalidate).map_err(Some),
+                    ("stale-if-error", secs) => secs.parse().map(StaleIfError).map_err(Some),
+                    (left, right) => Ok(Extension(left.to_owned(), Some(unescape(right))))
                 },
                 Some(_) => Err(None),
                 None => Ok(Extension(s.to_owned(), None))
@@ -175,6 +314,7 @@ impl FromStr for CacheDirective {
 #[cfg(test)]
 mod tests {
     use header::{Header, Raw};
+    use test_support::assert_roundtrip;
     use super::*;
 
     #[test]
@@ -209,6 +349,133 @@ mod tests {
             CacheDirective::Extension("bar".to_owned(), Some("baz".to_owned()))])))
     }
 
+    #[test]
+    fn test_parse_stale_and_immutable() {
+        let r: Raw = "max-age=600, stale-while-revalidate=30, stale-if-error=86400, immutable".into();
+        let cache: CacheControl = Header::parse_header(&r).unwrap();
+        assert_eq!(cache, CacheControl(vec![
+            CacheDirective::MaxAge(600),
+            CacheDirective::StaleWhileRevalidate(30),
+            CacheDirective::StaleIfError(86400),
+            CacheDirective::Immutable]));
+        assert_eq!(cache.to_string(),
+                   "max-age=600, stale-while-revalidate=30, stale-if-error=86400, immutable");
+        assert_roundtrip::<CacheControl>(
+            b"max-age=600, stale-while-revalidate=30, stale-if-error=86400, immutable");
+
+        let r: Raw = "stale-while-revalidated=30".into();
+        let cache: CacheControl = Header::parse_header(&r).unwrap();
+        assert_eq!(cache, CacheControl(vec![
+            CacheDirective::Extension("stale-while-revalidated".to_owned(), Some("30".to_owned()))]));
+    }
+
+    #[test]
+    fn test_must_understand() {
+        let r: Raw = "must-understand, no-store".into();
+        let cache: CacheControl = Header::parse_header(&r).unwrap();
+        assert_eq!(cache, CacheControl(vec![CacheDirective::MustUnderstand,
+                                            CacheDirective::NoStore]));
+        assert!(cache.contains(&CacheDirective::MustUnderstand));
+        assert!(cache.prevents_storage());
+        assert_eq!(cache.to_string(), "must-understand, no-store");
+        assert_roundtrip::<CacheControl>(b"must-understand, no-store");
+
+        assert!(CacheDirective::MustUnderstand.is_valid_in(DirectiveContext::Response));
+        assert!(!CacheDirective::MustUnderstand.is_valid_in(DirectiveContext::Request));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        assert_roundtrip::<CacheControl>(b"no-cache, private");
+        assert_roundtrip::<CacheControl>(b"max-age=100, s-maxage=\"20\", must-revalidate");
+        assert_roundtrip::<CacheControl>(b"foo, bar=baz, community=\"UCI\"");
+        assert_roundtrip::<CacheControl>(b"foo=\"\", bar=\"a b\"");
+    }
+
+    #[test]
+    fn test_extension_quoting() {
+        let cache = CacheControl(vec![
+            CacheDirective::Extension("community".to_owned(), Some("UCI, Irvine".to_owned())),
+            CacheDirective::Extension("note".to_owned(), Some("say \"hi\" \\o/".to_owned())),
+            CacheDirective::NoCache]);
+        let s = cache.to_string();
+        assert_eq!(s, "community=\"UCI, Irvine\", note=\"say \\\"hi\\\" \\\\o/\", no-cache");
+        let r: Raw = s.into();
+        assert_eq!(CacheControl::parse_header(&r).unwrap(), cache);
+
+        let cache = CacheControl(vec![
+            CacheDirective::Extension("x".to_owned(), Some("a\"".to_owned())),
+            CacheDirective::Extension("y".to_owned(), Some("\"".to_owned()))]);
+        let s = cache.to_string();
+        assert_eq!(s, "x=\"a\\\"\", y=\"\\\"\"");
+        let r: Raw = s.into();
+        assert_eq!(CacheControl::parse_header(&r).unwrap(), cache);
+    }
+
+    #[test]
+    fn test_validate_request() {
+        let r: Raw = "max-stale=10, public, no-cache, s-maxage=5, only-if-cached, x-ext".into();
+        let cache = CacheControl::parse_header(&r).unwrap();
+        assert_eq!(cache.validate_for(DirectiveContext::Request),
+                   Err(vec![&CacheDirective::Public, &CacheDirective::SMaxAge(5)]));
+
+        let r: Raw = "max-age=0, min-fresh=60, stale-if-error=30".into();
+        let cache = CacheControl::parse_header(&r).unwrap();
+        assert_eq!(cache.validate_for(DirectiveContext::Request), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_response() {
+        let r: Raw = "private, only-if-cached, max-age=60, min-fresh=1".into();
+        let cache = CacheControl::parse_header(&r).unwrap();
+        assert_eq!(cache.validate_for(DirectiveContext::Response),
+                   Err(vec![&CacheDirective::OnlyIfCached, &CacheDirective::MinFresh(1)]));
+
+        let r: Raw = "public, immutable, stale-while-revalidate=30, no-store".into();
+        let cache = CacheControl::parse_header(&r).unwrap();
+        assert_eq!(cache.validate_for(DirectiveContext::Response), Ok(()));
+    }
+
+    #[test]
+    fn test_revalidation_and_storage() {
+        let r: Raw = "no-cache".into();
+        let cache = CacheControl::parse_header(&r).unwrap();
+        assert!(cache.requires_revalidation());
+        assert!(!cache.prevents_storage());
+
+        let r: Raw = "no-store".into();
+        let cache = CacheControl::parse_header(&r).unwrap();
+        assert!(!cache.requires_revalidation());
+        assert!(cache.prevents_storage());
+
+        let r: Raw = "max-age=0, must-revalidate".into();
+        let cache = CacheControl::parse_header(&r).unwrap();
+        assert!(cache.requires_revalidation());
+        assert!(!cache.prevents_storage());
+
+        // not yet stale
+        let r: Raw = "max-age=60, must-revalidate".into();
+        let cache = CacheControl::parse_header(&r).unwrap();
+        assert!(!cache.requires_revalidation());
+
+        let r: Raw = "max-age=0".into();
+        let cache = CacheControl::parse_header(&r).unwrap();
+        assert!(!cache.requires_revalidation());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let cache = "no-cache, max-age=60".parse::<CacheControl>().unwrap();
+        assert_eq!(cache, CacheControl(vec![CacheDirective::NoCache, CacheDirective::MaxAge(60)]));
+        assert!("foo=".parse::<CacheControl>().is_err());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(CacheControl::default(), CacheControl(vec![]));
+        assert!(!CacheControl::default().requires_revalidation());
+    }
+
     #[test]
     fn test_parse_bad_syntax() {
         let r: Raw = vec![b"foo=".to_vec()].into();
@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+header! {
+    /// `Clear-Site-Data` header, defined in
+    /// [Clear Site Data](https://www.w3.org/TR/clear-site-data/#header)
+    ///
+    /// The `Clear-Site-Data` header field is sent in a response to direct the
+    /// client to clear the browsing data of the response's origin, of the
+    /// listed types.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Clear-Site-Data = 1#( quoted-string )
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `"cache"`
+    /// * `"cookies", "storage"`
+    /// * `"*"`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, ClearSiteData, DataType};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(ClearSiteData(vec![DataType::Cache, DataType::Cookies]));
+    /// ```
+    (ClearSiteData, "Clear-Site-Data") => (DataType)+
+
+    test_clear_site_data {
+        test_header!(test1, vec![b"\"cache\""]);
+        test_header!(test2, vec![b"\"cookies\", \"storage\", \"executionContexts\""]);
+        test_header!(
+            test_wildcard,
+            vec![b"\"*\""],
+            Some(HeaderField(vec![DataType::Wildcard])));
+        test_header!(
+            test_unknown,
+            vec![b"\"cache\", \"prefetchCache\""],
+            Some(HeaderField(vec![
+                DataType::Cache,
+                DataType::Ext("prefetchCache".to_owned()),
+            ])));
+
+        #[test]
+        fn test_unquoted_skipped() {
+            let raw: Raw = "cache, \"cookies\"".into();
+            let clear = HeaderField::parse_header(&raw).unwrap();
+            assert_eq!(clear, HeaderField(vec![DataType::Cookies]));
+        }
+
+        #[test]
+        fn test_effective_types_wildcard() {
+            let clear = HeaderField(vec![DataType::Cookies, DataType::Wildcard]);
+            let types = clear.effective_types();
+            assert_eq!(types.len(), 4);
+            assert!(types.contains(&DataType::Cache));
+            assert!(types.contains(&DataType::Cookies));
+            assert!(types.contains(&DataType::Storage));
+            assert!(types.contains(&DataType::ExecutionContexts));
+        }
+
+        #[test]
+        fn test_effective_types_list() {
+            let clear = HeaderField(vec![
+                DataType::Cache,
+                DataType::Storage,
+                DataType::Cache,
+                DataType::Ext("prefetchCache".to_owned()),
+            ]);
+            let types = clear.effective_types();
+            assert_eq!(types.len(), 3);
+            assert!(types.contains(&DataType::Cache));
+            assert!(types.contains(&DataType::Storage));
+            assert!(types.contains(&DataType::Ext("prefetchCache".to_owned())));
+        }
+    }
+}
+
+__hyper__list!(ClearSiteData => DataType);
+__hyper__from_str!(ClearSiteData);
+
+impl ClearSiteData {
+    /// Return the set of data types to clear, with the `"*"` wildcard
+    /// expanded to all known types.
+    ///
+    /// Unknown types are included as listed, for the client to ignore or
+    /// handle as it sees fit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{ClearSiteData, DataType};
+    ///
+    /// let clear = ClearSiteData(vec![DataType::Wildcard]);
+    /// assert!(clear.effective_types().contains(&DataType::Storage));
+    /// ```
+    pub fn effective_types(&self) -> HashSet<DataType> {
+        let mut types = HashSet::new();
+        for data_type in &self.0 {
+            if *data_type == DataType::Wildcard {
+                types.extend(DataType::ALL.iter().cloned());
+            } else {
+                types.insert(data_type.clone());
+            }
+        }
+        types
+    }
+}
+
+/// A type of browsing data, as listed by the `Clear-Site-Data` header.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum DataType {
+    /// `"cache"`
+    Cache,
+    /// `"cookies"`
+    Cookies,
+    /// `"storage"`
+    Storage,
+    /// `"executionContexts"`
+    ExecutionContexts,
+    /// `"*"`, all types
+    Wildcard,
+    /// Some other type, without the quotes
+    Ext(String),
+}
+
+impl DataType {
+    /// All the known data types, to which `Wildcard` expands.
+    pub const ALL: [DataType; 4] = [
+        DataType::Cache,
+        DataType::Cookies,
+        DataType::Storage,
+        DataType::ExecutionContexts,
+    ];
+}
+
+impl Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            DataType::Cache => "cache",
+            DataType::Cookies => "cookies",
+            DataType::Storage => "storage",
+            DataType::ExecutionContexts => "executionContexts",
+            DataType::Wildcard => "*",
+            DataType::Ext(ref name) => name,
+        };
+        write!(f, "\"{}\"", name)
+    }
+}
+
+impl FromStr for DataType {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> ::Result<DataType> {
+        if s.len() < 2 || !s.starts_with('"') || !s.ends_with('"') {
+            return Err(::Error::Header);
+        }
+        Ok(match &s[1..s.len() - 1] {
+            "cache" => DataType::Cache,
+            "cookies" => DataType::Cookies,
+            "storage" => DataType::Storage,
+            "executionContexts" => DataType::ExecutionContexts,
+            "*" => DataType::Wildcard,
+            name => DataType::Ext(name.to_owned()),
+        })
+    }
+}
+
+bench_header!(bench, ClearSiteData, { vec![b"\"cache\", \"cookies\", \"storage\"".to_vec()] });
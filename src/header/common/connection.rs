@@ -8,7 +8,7 @@ static KEEP_ALIVE: &'static str = "keep-alive";
 static CLOSE: &'static str = "close";
 
 /// Values that can be in the `Connection` header.
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
 pub enum ConnectionOption {
     /// The `keep-alive` connection value.
     KeepAlive,
@@ -105,6 +105,16 @@ header! {
     }
 }
 
+__hyper__list!(Connection => ConnectionOption);
+__hyper__from_str!(Connection);
+
+impl Default for Connection {
+    /// An empty `Connection`, with no options.
+    fn default() -> Connection {
+        Connection(Vec::new())
+    }
+}
+
 impl Connection {
     /// A constructor to easily create a `Connection: close` header.
     #[inline]
@@ -117,6 +127,49 @@ impl Connection {
     pub fn keep_alive() -> Connection {
         Connection(vec![ConnectionOption::KeepAlive])
     }
+
+    /// Returns true if the `close` option is listed.
+    pub fn contains_close(&self) -> bool {
+        self.0.contains(&ConnectionOption::Close)
+    }
+
+    /// Returns true if the `keep-alive` option is listed.
+    pub fn contains_keep_alive(&self) -> bool {
+        self.0.contains(&ConnectionOption::KeepAlive)
+    }
+
+    /// Returns true if the given header name is listed, compared
+    /// case-insensitively.
+    pub fn contains_header_name(&self, name: &str) -> bool {
+        self.header_names().any(|n| n.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterate the listed connection-specific header names, which a proxy
+    /// must remove before forwarding a message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{Connection, Headers};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set_raw("Connection", "close, X-Trace");
+    /// headers.set_raw("X-Trace", "1");
+    ///
+    /// let names = headers.get::<Connection>()
+    ///     .map(|conn| conn.header_names().map(str::to_owned).collect())
+    ///     .unwrap_or_else(Vec::new);
+    /// for name in names {
+    ///     headers.remove_raw(&name);
+    /// }
+    /// assert!(headers.get_raw("x-trace").is_none());
+    /// ```
+    pub fn header_names(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|opt| match *opt {
+            ConnectionOption::ConnectionHeader(ref name) => Some(&name[..]),
+            _ => None,
+        })
+    }
 }
 
 bench_header!(close, Connection, { vec![b"close".to_vec()] });
@@ -127,8 +180,78 @@ bench_header!(header, Connection, { vec![b"authorization".to_vec()] });
 mod tests {
     use super::{Connection,ConnectionHeader};
     use header::{Header, Raw};
+    use test_support::assert_roundtrip;
     use unicase::Ascii;
 
+    #[test]
+    fn test_hash_dedup() {
+        use std::collections::HashSet;
+        use super::ConnectionOption;
+
+        let options = vec![
+            ConnectionHeader(Ascii::new("Upgrade".to_owned())),
+            ConnectionHeader(Ascii::new("upgrade".to_owned())),
+            ConnectionHeader(Ascii::new("X-CUSTOM".to_owned())),
+            ConnectionOption::KeepAlive,
+            "keep-alive".parse().unwrap(),
+            ConnectionHeader(Ascii::new("x-custom".to_owned())),
+        ];
+        let set = options.into_iter().collect::<HashSet<ConnectionOption>>();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&ConnectionHeader(Ascii::new("UPGRADE".to_owned()))));
+        assert!(set.contains(&ConnectionOption::KeepAlive));
+    }
+
+    #[test]
+    fn test_list_methods() {
+        use super::ConnectionOption;
+
+        let mut conn = Connection::keep_alive();
+        assert!(!conn.contains(&ConnectionOption::Close));
+        conn.push(ConnectionOption::Close);
+        assert!(conn.contains(&ConnectionOption::Close));
+        assert_eq!(conn.remove(0), ConnectionOption::KeepAlive);
+        assert_eq!(conn, Connection::close());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let conn = "keep-alive, Upgrade".parse::<Connection>().unwrap();
+        assert!(conn.contains_keep_alive());
+        assert!(conn.contains_header_name("upgrade"));
+        assert_eq!("close".parse::<Connection>().unwrap(), Connection::close());
+    }
+
+    #[test]
+    fn test_default() {
+        let conn = Connection::default();
+        assert_eq!(conn, Connection(vec![]));
+        assert!(!conn.contains_close() && !conn.contains_keep_alive());
+    }
+
+    #[test]
+    fn test_queries() {
+        let conn = parse_option(b"Keep-Alive, X-Trace, te".to_vec());
+        assert!(conn.contains_keep_alive());
+        assert!(!conn.contains_close());
+        assert_eq!(conn.header_names().collect::<Vec<_>>(), vec!["X-Trace", "te"]);
+        assert!(conn.contains_header_name("x-trace"));
+        assert!(conn.contains_header_name("TE"));
+        assert!(!conn.contains_header_name("keep-alive"));
+
+        let conn = Connection::close();
+        assert!(conn.contains_close());
+        assert!(!conn.contains_keep_alive());
+        assert_eq!(conn.header_names().count(), 0);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        assert_roundtrip::<Connection>(b"close");
+        assert_roundtrip::<Connection>(b"Keep-Alive, Upgrade");
+        assert_roundtrip::<Connection>(b"upgrade, x-custom,  te");
+    }
+
     fn parse_option(header: Vec<u8>) -> Connection {
         let val: Raw = header.into();
         let connection: Connection = Header::parse_header(&val).unwrap();
@@ -15,13 +15,17 @@ use header::parsing::{parse_extended_value, http_percent_encode};
 use header::shared::Charset;
 
 /// The implied disposition of the content of the HTTP body.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum DispositionType {
     /// Inline implies default processing
     Inline,
     /// Attachment implies that the recipient should prompt the user to save the response locally,
     /// rather than process it normally (as per its media type).
     Attachment,
+    /// FormData implies a field of a `multipart/form-data` body, as defined in
+    /// [RFC7578](https://tools.ietf.org/html/rfc7578#section-4.2), with the
+    /// field name given by the `name` parameter.
+    FormData,
     /// Extension type.  Should be handled by recipients the same way as Attachment
     Ext(String)
 }
@@ -50,7 +54,7 @@ pub enum DispositionParam {
 /// content-disposition = "Content-Disposition" ":"
 ///                       disposition-type *( ";" disposition-parm )
 ///
-/// disposition-type    = "inline" | "attachment" | disp-ext-type
+/// disposition-type    = "inline" | "attachment" | "form-data" | disp-ext-type
 ///                       ; case-insensitive
 ///
 /// disp-ext-type       = token
@@ -89,6 +93,34 @@ pub struct ContentDisposition {
     pub parameters: Vec<DispositionParam>,
 }
 
+impl ContentDisposition {
+    /// Returns true if the disposition type is `inline`.
+    pub fn is_inline(&self) -> bool {
+        self.disposition == DispositionType::Inline
+    }
+
+    /// Returns true if the disposition type is `attachment`.
+    pub fn is_attachment(&self) -> bool {
+        self.disposition == DispositionType::Attachment
+    }
+
+    /// Returns true if the disposition type is `form-data`.
+    pub fn is_form_data(&self) -> bool {
+        self.disposition == DispositionType::FormData
+    }
+
+    /// Get the value of the `name` parameter, the field name of a
+    /// `form-data` disposition, if present.
+    pub fn name(&self) -> Option<&str> {
+        self.parameters.iter().filter_map(|param| match *param {
+            DispositionParam::Ext(ref k, ref v) if unicase::eq_ascii(&**k, "name") => {
+                Some(&v[..])
+            },
+            _ => None,
+        }).next()
+    }
+}
+
 impl Header for ContentDisposition {
     fn header_name() -> &'static str {
         static NAME: &'static str = "Content-Disposition";
@@ -110,6 +142,8 @@ impl Header for ContentDisposition {
                     DispositionType::Inline
                 } else if unicase::eq_ascii(&*disposition, "attachment") {
                     DispositionType::Attachment
+                } else if unicase::eq_ascii(&*disposition, "form-data") {
+                    DispositionType::FormData
                 } else {
                     DispositionType::Ext(disposition.to_owned())
                 },
@@ -155,11 +189,14 @@ impl Header for ContentDisposition {
     }
 }
 
+__hyper__from_str!(ContentDisposition);
+
 impl fmt::Display for ContentDisposition {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.disposition {
             DispositionType::Inline => try!(write!(f, "inline")),
             DispositionType::Attachment => try!(write!(f, "attachment")),
+            DispositionType::FormData => try!(write!(f, "form-data")),
             DispositionType::Ext(ref s) => try!(write!(f, "{}", s)),
         }
         for param in &self.parameters {
@@ -209,7 +246,7 @@ mod tests {
         let a: Raw = "form-data; dummy=3; name=upload;\r\n filename=\"sample.png\"".into();
         let a: ContentDisposition = ContentDisposition::parse_header(&a).unwrap();
         let b = ContentDisposition {
-            disposition: DispositionType::Ext("form-data".to_owned()),
+            disposition: DispositionType::FormData,
             parameters: vec![
                 DispositionParam::Ext("dummy".to_owned(), "3".to_owned()),
                 DispositionParam::Ext("name".to_owned(), "upload".to_owned()),
@@ -246,6 +283,34 @@ mod tests {
         assert_eq!(a, b);
     }
 
+    #[test]
+    fn test_disposition_types() {
+        let a: Raw = "attachment; filename=\"x\"".into();
+        let a: ContentDisposition = ContentDisposition::parse_header(&a).unwrap();
+        assert!(a.is_attachment());
+        assert!(!a.is_inline());
+        assert!(!a.is_form_data());
+        assert_eq!(a.name(), None);
+
+        let a: Raw = "INLINE".into();
+        let a: ContentDisposition = ContentDisposition::parse_header(&a).unwrap();
+        assert_eq!(a.disposition, DispositionType::Inline);
+        assert!(a.is_inline());
+        assert!(a.parameters.is_empty());
+
+        let a: Raw = "form-data; name=\"field\"".into();
+        let a: ContentDisposition = ContentDisposition::parse_header(&a).unwrap();
+        assert_eq!(a.disposition, DispositionType::FormData);
+        assert!(a.is_form_data());
+        assert_eq!(a.name(), Some("field"));
+        assert_eq!(format!("{}", a), "form-data; name=\"field\"");
+
+        let a: Raw = "x-custom; Name=other".into();
+        let a: ContentDisposition = ContentDisposition::parse_header(&a).unwrap();
+        assert_eq!(a.disposition, DispositionType::Ext("x-custom".to_owned()));
+        assert_eq!(a.name(), Some("other"));
+    }
+
     #[test]
     fn test_display() {
         let as_string = "attachment; filename*=UTF-8'en'%C2%A3%20and%20%E2%82%AC%20rates";
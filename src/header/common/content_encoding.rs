@@ -1,4 +1,7 @@
-use header::Encoding;
+use std::fmt;
+
+use header::{ContentType, Encoding};
+use mime::{self, Mime};
 
 header! {
     /// `Content-Encoding` header, defined in
@@ -28,7 +31,7 @@ header! {
     /// use hyperx::header::{Headers, ContentEncoding, Encoding};
     /// 
     /// let mut headers = Headers::new();
-    /// headers.set(ContentEncoding(vec![Encoding::Chunked]));
+    /// headers.set(ContentEncoding(vec![Encoding::Gzip]));
     /// ```
     ///
     /// ```
@@ -36,20 +39,144 @@ header! {
     /// 
     /// let mut headers = Headers::new();
     /// headers.set(
-    ///     ContentEncoding(vec![
-    ///         Encoding::Gzip,
-    ///         Encoding::Chunked,
-    ///     ])
+    ///     ContentEncoding(vec![Encoding::Gzip])
     /// );
     /// ```
-    (ContentEncoding, "Content-Encoding") => (Encoding)+
+    ///
+    /// Parsing fails for codings which are not valid content-codings, such
+    /// as `chunked`.
+    (ContentEncoding, "Content-Encoding") => (Encoding)+ if is_content_coding
 
     test_content_encoding {
         /// Testcase from the RFC
         test_header!(test1, vec![b"gzip"], Some(ContentEncoding(vec![Encoding::Gzip])));
+        test_header!(test_chunked, vec![b"chunked"], None::<ContentEncoding>);
+        test_header!(test_trailers, vec![b"gzip, trailers"], None::<ContentEncoding>);
+        test_header!(test_chunked_uppercase, vec![b"CHUNKED"], None::<ContentEncoding>);
+        test_header!(test_empty, vec![b""], Some(ContentEncoding(vec![])));
+
+        #[test]
+        fn test_double_compression_benign() {
+            let ce = HeaderField(vec![Encoding::Gzip]);
+            assert_eq!(ce.double_compression(&ContentType::text_utf8()), None);
+            assert_eq!(ce.double_compression(&ContentType::json()), None);
+            let ce = HeaderField(vec![Encoding::Brotli]);
+            assert_eq!(ce.double_compression(&ContentType::html()), None);
+            let ce = HeaderField(vec![Encoding::Identity]);
+            let gzip = ContentType("application/gzip".parse().unwrap());
+            assert_eq!(ce.double_compression(&gzip), None);
+        }
+
+        #[test]
+        fn test_double_compression_conflict() {
+            let gzip = ContentType("application/gzip".parse().unwrap());
+            let ce = HeaderField(vec![Encoding::Gzip]);
+            let warning = ce.double_compression(&gzip).unwrap();
+            assert_eq!(warning.encoding, Encoding::Gzip);
+            assert_eq!(warning.media_type, gzip.0);
+            assert_eq!(warning.to_string(),
+                       "content-coding gzip applied to compressed media type application/gzip");
+
+            let ce = HeaderField(vec![Encoding::Identity, Encoding::Brotli]);
+            let zip = ContentType("application/zip".parse().unwrap());
+            assert_eq!(ce.double_compression(&zip).unwrap().encoding, Encoding::Brotli);
+            let tgz = ContentType("application/tar+gzip".parse().unwrap());
+            assert!(ce.double_compression(&tgz).is_some());
+
+            let r: Raw = "GZIP".into();
+            let ce = HeaderField::parse_header(&r).unwrap();
+            assert!(ce.double_compression(&gzip).is_some());
+            let ce = HeaderField(vec![Encoding::EncodingExt("ZSTD".to_owned())]);
+            assert!(ce.double_compression(&gzip).is_some());
+        }
     }
 }
 
+__hyper__list!(ContentEncoding => Encoding);
+__hyper__from_str!(ContentEncoding);
+
+impl ContentEncoding {
+    /// Check for a compressing content-coding applied to a `Content-Type`
+    /// which is itself a compressed format, such as `Content-Encoding: gzip`
+    /// with `Content-Type: application/gzip`.
+    ///
+    /// This is usually a server misconfiguration, either double compressing
+    /// the body or mislabeling a compressed file so that clients decompress
+    /// it on download. A text or other uncompressed type with a compressing
+    /// coding is fine. This is only a warning: the message is still valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{ContentEncoding, ContentType, Encoding};
+    ///
+    /// let ce = ContentEncoding(vec![Encoding::Gzip]);
+    /// assert_eq!(ce.double_compression(&ContentType::html()), None);
+    ///
+    /// let gzip = ContentType("application/gzip".parse().unwrap());
+    /// assert!(ce.double_compression(&gzip).is_some());
+    /// ```
+    pub fn double_compression(&self, content_type: &ContentType) -> Option<DoubleCompression> {
+        if !is_compressed_type(content_type) {
+            return None;
+        }
+        self.iter().find(|encoding| is_compressing(encoding)).map(|encoding| {
+            DoubleCompression {
+                media_type: content_type.0.clone(),
+                encoding: encoding.clone(),
+            }
+        })
+    }
+}
+
+/// A compressing content-coding applied to an already compressed media type,
+/// as returned by `ContentEncoding::double_compression` and
+/// `Headers::check_content_coding`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DoubleCompression {
+    /// The compressed media type, from `Content-Type`.
+    pub media_type: Mime,
+    /// The first compressing coding, from `Content-Encoding`.
+    pub encoding: Encoding,
+}
+
+impl fmt::Display for DoubleCompression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "content-coding {} applied to compressed media type {}",
+               self.encoding, self.media_type)
+    }
+}
+
+fn is_compressed_type(mime: &Mime) -> bool {
+    if mime.type_() != mime::APPLICATION {
+        return false;
+    }
+    match mime.subtype().as_str() {
+        "gzip" | "x-gzip" | "zip" | "zstd" | "x-bzip2" | "x-xz" | "x-compress" |
+        "x-7z-compressed" | "x-brotli" => true,
+        _ => mime.suffix().map_or(false, |suffix| {
+            suffix == "gzip" || suffix == "zip" || suffix == "zstd"
+        }),
+    }
+}
+
+fn is_compressing(encoding: &Encoding) -> bool {
+    match *encoding {
+        Encoding::Gzip | Encoding::Deflate | Encoding::Brotli | Encoding::Compress |
+        Encoding::DictionaryBrotli(_) | Encoding::DictionaryZstd(_) => true,
+        Encoding::EncodingExt(ref ext) => {
+            ["gzip", "deflate", "br", "compress", "dcb", "dcz", "zstd", "x-gzip", "x-compress"]
+                .iter()
+                .any(|name| ext.eq_ignore_ascii_case(name))
+        },
+        _ => false,
+    }
+}
+
+fn is_content_coding(encoding: &Encoding) -> bool {
+    encoding.is_valid_content_coding()
+}
+
 standard_header!(ContentEncoding, CONTENT_ENCODING);
 
 bench_header!(single, ContentEncoding, { vec![b"gzip".to_vec()] });
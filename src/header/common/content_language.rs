@@ -62,4 +62,7 @@ header! {
     }
 }
 
+__hyper__list!(ContentLanguage => QualityItem<LanguageTag>);
+__hyper__from_str!(ContentLanguage);
+
 standard_header!(ContentLanguage, CONTENT_LANGUAGE);
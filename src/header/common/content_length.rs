@@ -49,6 +49,11 @@ impl Header for ContentLength {
         NAME
     }
 
+    #[inline]
+    fn max_occurrences() -> Option<usize> {
+        Some(1)
+    }
+
     fn parse_header<'a, T>(raw: &'a T) -> ::Result<ContentLength>
     where T: RawLike<'a>
     {
@@ -75,6 +80,8 @@ impl Header for ContentLength {
     }
 }
 
+__hyper__from_str!(ContentLength);
+
 standard_header!(ContentLength, CONTENT_LENGTH);
 
 impl fmt::Display for ContentLength {
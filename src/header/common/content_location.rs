@@ -46,4 +46,6 @@ header! {
     }
 }
 
+__hyper__from_str!(ContentLocation);
+
 standard_header!(ContentLocation, CONTENT_LOCATION);
@@ -1,5 +1,7 @@
 use std::fmt::{self, Display};
-use std::str::FromStr;
+use std::str::{self, FromStr};
+
+use header::ContentType;
 
 header! {
     /// `Content-Range` header, defined in
@@ -63,9 +65,53 @@ header! {
             vec![b"bytes 1-2-3/500"],
             None::<ContentRange>);
 
+        #[test]
+        fn test_parse_byteranges() {
+            let body = b"preamble\r\n\
+                --THIS_STRING_SEPARATES\r\n\
+                Content-Type: application/pdf\r\n\
+                Content-Range: bytes 500-503/8000\r\n\
+                \r\n\
+                abcd\r\n\
+                --THIS_STRING_SEPARATES  \r\n\
+                content-range: bytes 7000-7001/8000\r\n\
+                \r\n\
+                ef\r\n\
+                --THIS_STRING_SEPARATES--\r\n";
+            let parts = parse_byteranges(body, "THIS_STRING_SEPARATES").unwrap();
+            assert_eq!(parts.len(), 2);
+
+            assert_eq!(parts[0].content_range, ContentRange(ContentRangeSpec::Bytes {
+                range: Some((500, 503)),
+                instance_length: Some(8000)
+            }));
+            assert_eq!(parts[0].content_type, Some(ContentType("application/pdf".parse().unwrap())));
+            assert_eq!(parts[0].body, b"abcd");
+
+            assert_eq!(parts[1].content_range, ContentRange(ContentRangeSpec::Bytes {
+                range: Some((7000, 7001)),
+                instance_length: Some(8000)
+            }));
+            assert_eq!(parts[1].content_type, None);
+            assert_eq!(parts[1].body, b"ef");
+        }
+
+        #[test]
+        fn test_parse_byteranges_invalid() {
+            // missing close delimiter
+            assert!(parse_byteranges(
+                b"--b\r\nContent-Range: bytes 0-0/1\r\n\r\na", "b").is_err());
+            // missing Content-Range
+            assert!(parse_byteranges(
+                b"--b\r\nContent-Type: text/plain\r\n\r\na\r\n--b--", "b").is_err());
+            // no delimiter
+            assert!(parse_byteranges(b"abcd", "b").is_err());
+        }
     }
 }
 
+__hyper__from_str!(ContentRange);
+
 
 /// Content-Range, described in [RFC7233](https://tools.ietf.org/html/rfc7233#section-4.2)
 ///
@@ -189,4 +235,99 @@ impl Display for ContentRangeSpec {
     }
 }
 
+/// A part of a `multipart/byteranges` body, returned by `parse_byteranges`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ByteRangesPart<'a> {
+    /// The `Content-Range` of the part.
+    pub content_range: ContentRange,
+    /// The `Content-Type` of the part, if any.
+    pub content_type: Option<ContentType>,
+    /// The part payload.
+    pub body: &'a [u8],
+}
+
+/// Split the body of a `multipart/byteranges` response into its parts.
+///
+/// This is the body of a 206 (Partial Content) response to a request for
+/// multiple ranges, as defined in
+/// [RFC7233](https://tools.ietf.org/html/rfc7233#appendix-A). The
+/// `boundary` is that of the response `Content-Type`, as returned by
+/// `ContentType::multipart_boundary`. Each part must have a `Content-Range`
+/// header, while other part headers besides `Content-Type` are ignored. Any
+/// preamble and epilogue are skipped. The length of each payload is not
+/// checked against its `Content-Range`.
+///
+/// # Example
+/// ```
+/// use hyperx::header::parse_byteranges;
+///
+/// let body = b"--sep\r\n\
+///     Content-Range: bytes 0-2/10\r\n\r\n\
+///     abc\r\n\
+///     --sep\r\n\
+///     Content-Range: bytes 8-9/10\r\n\r\n\
+///     ij\r\n\
+///     --sep--";
+/// let parts = parse_byteranges(body, "sep").unwrap();
+/// assert_eq!(parts[0].body, b"abc");
+/// assert_eq!(parts[1].body, b"ij");
+/// ```
+pub fn parse_byteranges<'a>(body: &'a [u8], boundary: &str)
+    -> ::Result<Vec<ByteRangesPart<'a>>>
+{
+    let delimiter = format!("\r\n--{}", boundary);
+    let delimiter = delimiter.as_bytes();
+    // The first delimiter need not be preceded by CRLF, without a preamble
+    let mut pos = if body.starts_with(&delimiter[2..]) {
+        delimiter.len() - 2
+    } else {
+        try!(find(body, delimiter).ok_or(::Error::Header)) + delimiter.len()
+    };
+    let mut parts = Vec::new();
+    loop {
+        let rest = &body[pos..];
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+        let padding = rest.iter().take_while(|&&b| b == b' ' || b == b'\t').count();
+        if !rest[padding..].starts_with(b"\r\n") {
+            return Err(::Error::Header);
+        }
+        let start = pos + padding + 2;
+        let end = start + try!(find(&body[start..], delimiter).ok_or(::Error::Header));
+        parts.push(try!(parse_part(&body[start..end])));
+        pos = end + delimiter.len();
+    }
+}
+
+fn parse_part<'a>(part: &'a [u8]) -> ::Result<ByteRangesPart<'a>> {
+    let (head, body) = if part.starts_with(b"\r\n") {
+        (&part[..0], &part[2..])
+    } else {
+        let i = try!(find(part, b"\r\n\r\n").ok_or(::Error::Header));
+        (&part[..i], &part[i + 4..])
+    };
+    let head = try!(str::from_utf8(head).map_err(|_| ::Error::Header));
+    let mut content_range = None;
+    let mut content_type = None;
+    for line in head.split("\r\n").filter(|line| !line.is_empty()) {
+        let (name, value) = try!(split_in_two(line, ':').ok_or(::Error::Header));
+        let name = name.trim();
+        if name.eq_ignore_ascii_case("Content-Range") {
+            content_range = Some(try!(value.trim().parse::<ContentRange>()));
+        } else if name.eq_ignore_ascii_case("Content-Type") {
+            content_type = Some(try!(value.trim().parse::<ContentType>()));
+        }
+    }
+    Ok(ByteRangesPart {
+        content_range: try!(content_range.ok_or(::Error::Header)),
+        content_type: content_type,
+        body: body,
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
 standard_header!(ContentRange, CONTENT_RANGE);
@@ -0,0 +1,452 @@
+use std::fmt;
+use std::str::FromStr;
+
+use base64;
+use unicase;
+
+use header::{Header, RawLike, parsing};
+
+/// `Content-Security-Policy` header, defined in
+/// [CSP Level 3](https://www.w3.org/TR/CSP3/#csp-header)
+///
+/// The `Content-Security-Policy` response header field restricts the
+/// resources a document may load or execute, as a list of directives, each
+/// a name followed by its source expressions.
+///
+/// Only a single policy is supported: a value holding several
+/// comma-separated policies is not parsed.
+///
+/// # ABNF
+///
+/// ```text
+/// serialized-policy    = serialized-directive *( OWS ";" [ OWS serialized-directive ] )
+/// serialized-directive = directive-name [ RWS directive-value ]
+/// directive-name       = 1*( ALPHA / DIGIT / "-" )
+/// directive-value      = *( %x09 / %x20-%x2B / %x2D-%x3A / %x3C-%x7E )
+/// ```
+///
+/// # Example values
+/// * `default-src 'self'`
+/// * `default-src 'self'; img-src *; script-src 'nonce-2726c7f26c'`
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{Headers, ContentSecurityPolicy, CspDirective, CspSource};
+///
+/// let mut headers = Headers::new();
+/// headers.set(ContentSecurityPolicy(vec![
+///     CspDirective::new("default-src", vec![CspSource::Self_]),
+///     CspDirective::new("img-src", vec![CspSource::Host("*".to_owned())]),
+/// ]));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct ContentSecurityPolicy(pub Vec<CspDirective>);
+
+__hyper__deref!(ContentSecurityPolicy => Vec<CspDirective>);
+
+/// A directive of a `Content-Security-Policy`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CspDirective {
+    /// The directive name, such as `script-src`.
+    pub name: String,
+    /// The source expressions of the directive.
+    ///
+    /// The values of directives which do not take a source list, such as
+    /// the path of `report-uri`, are kept as a `CspSource::Host`.
+    pub sources: Vec<CspSource>,
+}
+
+impl CspDirective {
+    /// Create a new directive from a name and its sources.
+    pub fn new<N: Into<String>>(name: N, sources: Vec<CspSource>) -> CspDirective {
+        CspDirective {
+            name: name.into(),
+            sources: sources,
+        }
+    }
+}
+
+/// A source expression of a `Content-Security-Policy` directive, defined in
+/// [CSP Level 3](https://www.w3.org/TR/CSP3/#framework-directive-source-list)
+///
+/// Keywords are matched case-insensitively, and formatted in lowercase with
+/// their single quotes. Nonce and hash values must be base64 or base64url.
+///
+/// # ABNF
+///
+/// ```text
+/// source-expression = scheme-source / host-source / keyword-source
+///                     / nonce-source / hash-source
+/// scheme-source     = scheme-part ":"
+/// keyword-source    = "'self'" / "'unsafe-inline'" / "'unsafe-eval'"
+///                     / "'strict-dynamic'" / "'unsafe-hashes'"
+///                     / "'report-sample'" / "'unsafe-allow-redirects'"
+///                     / "'wasm-unsafe-eval'"
+/// nonce-source      = "'nonce-" base64-value "'"
+/// hash-source       = "'" hash-algorithm "-" base64-value "'"
+/// hash-algorithm    = "sha256" / "sha384" / "sha512"
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum CspSource {
+    /// `'self'`
+    Self_,
+    /// `'none'`
+    None_,
+    /// `'unsafe-inline'`
+    UnsafeInline,
+    /// `'unsafe-eval'`
+    UnsafeEval,
+    /// `'strict-dynamic'`
+    StrictDynamic,
+    /// `'nonce-<value>'`, with the base64 value
+    Nonce(String),
+    /// `'<algorithm>-<value>'`, with the base64 digest value
+    Hash(CspHashAlgo, String),
+    /// `<scheme>:`, with the scheme only, such as `https`
+    Scheme(String),
+    /// A host source, such as `https://example.com`, `*.example.com` or `*`
+    Host(String),
+    /// Another quoted keyword, such as `'report-sample'`, without quotes
+    Keyword(String),
+}
+
+/// The hash algorithm of a `CspSource::Hash`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum CspHashAlgo {
+    /// `sha256`
+    Sha256,
+    /// `sha384`
+    Sha384,
+    /// `sha512`
+    Sha512,
+}
+
+impl CspHashAlgo {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            CspHashAlgo::Sha256 => "sha256",
+            CspHashAlgo::Sha384 => "sha384",
+            CspHashAlgo::Sha512 => "sha512",
+        }
+    }
+}
+
+impl fmt::Display for CspHashAlgo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CspSource {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> ::Result<CspSource> {
+        if s.is_empty() || s.contains(char::is_whitespace) {
+            return Err(::Error::Header);
+        }
+        if s.starts_with('\'') {
+            if s.len() < 2 || !s.ends_with('\'') {
+                return Err(::Error::Header);
+            }
+            let keyword = &s[1..s.len() - 1];
+            let lower = keyword.to_ascii_lowercase();
+            return Ok(match &lower[..] {
+                "self" => CspSource::Self_,
+                "none" => CspSource::None_,
+                "unsafe-inline" => CspSource::UnsafeInline,
+                "unsafe-eval" => CspSource::UnsafeEval,
+                "strict-dynamic" => CspSource::StrictDynamic,
+                _ => {
+                    let (prefix, value) = match keyword.find('-') {
+                        Some(idx) => (&lower[..idx], &keyword[idx + 1..]),
+                        None => (&lower[..], ""),
+                    };
+                    let algo = match prefix {
+                        "nonce" => None,
+                        "sha256" => Some(CspHashAlgo::Sha256),
+                        "sha384" => Some(CspHashAlgo::Sha384),
+                        "sha512" => Some(CspHashAlgo::Sha512),
+                        _ => return Ok(CspSource::Keyword(lower.clone())),
+                    };
+                    if !is_base64_value(value) {
+                        return Err(::Error::Header);
+                    }
+                    match algo {
+                        Some(algo) => CspSource::Hash(algo, value.to_owned()),
+                        None => CspSource::Nonce(value.to_owned()),
+                    }
+                }
+            });
+        }
+        if s.ends_with(':') && is_scheme(&s[..s.len() - 1]) {
+            return Ok(CspSource::Scheme(s[..s.len() - 1].to_owned()));
+        }
+        Ok(CspSource::Host(s.to_owned()))
+    }
+}
+
+// scheme-part = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )
+fn is_scheme(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    match bytes.next() {
+        Some(b) if b.is_ascii_alphabetic() => {
+            bytes.all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.')
+        }
+        _ => false,
+    }
+}
+
+impl fmt::Display for CspSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CspSource::Self_ => f.write_str("'self'"),
+            CspSource::None_ => f.write_str("'none'"),
+            CspSource::UnsafeInline => f.write_str("'unsafe-inline'"),
+            CspSource::UnsafeEval => f.write_str("'unsafe-eval'"),
+            CspSource::StrictDynamic => f.write_str("'strict-dynamic'"),
+            CspSource::Nonce(ref nonce) => write!(f, "'nonce-{}'", nonce),
+            CspSource::Hash(algo, ref digest) => write!(f, "'{}-{}'", algo, digest),
+            CspSource::Scheme(ref scheme) => write!(f, "{}:", scheme),
+            CspSource::Host(ref host) => f.write_str(host),
+            CspSource::Keyword(ref keyword) => write!(f, "'{}'", keyword),
+        }
+    }
+}
+
+impl ContentSecurityPolicy {
+    /// Get a directive by (case-insensitive) name.
+    pub fn directive(&self, name: &str) -> Option<&CspDirective> {
+        self.iter().find(|d| unicase::eq_ascii(&*d.name, name))
+    }
+
+    /// Add a `'nonce-<nonce>'` source to the named directive, creating the
+    /// directive if absent.
+    ///
+    /// Returns an error, leaving the policy unchanged, if `nonce` is not a
+    /// base64 or base64url value, as it could otherwise inject other
+    /// sources into the policy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{ContentSecurityPolicy, CspDirective, CspSource};
+    ///
+    /// let mut csp = ContentSecurityPolicy(vec![
+    ///     CspDirective::new("default-src", vec![CspSource::Self_]),
+    /// ]);
+    /// csp.with_nonce("script-src", "2726c7f26c").unwrap();
+    /// assert_eq!(csp.to_string(), "default-src 'self'; script-src 'nonce-2726c7f26c'");
+    ///
+    /// assert!(csp.with_nonce("script-src", "x' 'unsafe-inline").is_err());
+    /// ```
+    pub fn with_nonce(&mut self, directive: &str, nonce: &str) -> ::Result<()> {
+        if !is_base64_value(nonce) {
+            return Err(::Error::Header);
+        }
+        let source = CspSource::Nonce(nonce.to_owned());
+        if let Some(d) = self.0.iter_mut().find(|d| unicase::eq_ascii(&*d.name, directive)) {
+            if !d.sources.contains(&source) {
+                d.sources.push(source);
+            }
+            return Ok(());
+        }
+        self.0.push(CspDirective::new(directive, vec![source]));
+        Ok(())
+    }
+
+    /// Encode random bytes as a nonce for `with_nonce`.
+    ///
+    /// The bytes must come from a cryptographically secure random number
+    /// generator, and should be at least 16 bytes long, so that the nonce
+    /// cannot be guessed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::ContentSecurityPolicy;
+    ///
+    /// assert_eq!(ContentSecurityPolicy::nonce_from_bytes(&[0xff; 4]), "/////w==");
+    /// ```
+    pub fn nonce_from_bytes(bytes: &[u8]) -> String {
+        base64::encode(bytes)
+    }
+}
+
+// base64-value = 1*( ALPHA / DIGIT / "+" / "/" / "-" / "_" )*2( "=" )
+fn is_base64_value(s: &str) -> bool {
+    let padding = s.bytes().rev().take_while(|&b| b == b'=').count();
+    let value = &s[..s.len() - padding];
+    !value.is_empty() && padding <= 2 &&
+        value.bytes().all(|b| {
+            b.is_ascii_alphanumeric() || b == b'+' || b == b'/' || b == b'-' || b == b'_'
+        })
+}
+
+impl Header for ContentSecurityPolicy {
+    fn header_name() -> &'static str {
+        static NAME: &'static str = "Content-Security-Policy";
+        NAME
+    }
+
+    fn parse_header<'a, T>(raw: &'a T) -> ::Result<ContentSecurityPolicy>
+    where T: RawLike<'a>
+    {
+        parsing::from_one_raw_str(raw)
+            .and_then(|s: String| {
+                if s.contains(',') {
+                    return Err(::Error::Header);
+                }
+                let mut directives = Vec::new();
+                for d in s.split(';') {
+                    let mut tokens = d.split_whitespace();
+                    if let Some(name) = tokens.next() {
+                        let mut sources = Vec::new();
+                        for token in tokens {
+                            sources.push(try!(token.parse()));
+                        }
+                        directives.push(CspDirective::new(name, sources));
+                    }
+                }
+                if directives.is_empty() {
+                    Err(::Error::Header)
+                } else {
+                    Ok(ContentSecurityPolicy(directives))
+                }
+            })
+    }
+
+    fn fmt_header(&self, f: &mut ::header::Formatter) -> fmt::Result {
+        f.fmt_line(self)
+    }
+}
+
+__hyper__from_str!(ContentSecurityPolicy);
+
+impl fmt::Display for ContentSecurityPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, directive) in self.iter().enumerate() {
+            if i > 0 {
+                try!(f.write_str("; "));
+            }
+            try!(fmt::Display::fmt(directive, f));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CspDirective {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(f.write_str(&self.name));
+        for source in &self.sources {
+            try!(write!(f, " {}", source));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContentSecurityPolicy, CspDirective, CspHashAlgo, CspSource};
+    use header::{Header, Raw};
+
+    #[test]
+    fn test_parse() {
+        let r: Raw = "default-src 'self';  img-src * data: ;".into();
+        let csp: ContentSecurityPolicy = Header::parse_header(&r).unwrap();
+        assert_eq!(csp, ContentSecurityPolicy(vec![
+            CspDirective::new("default-src", vec![CspSource::Self_]),
+            CspDirective::new("img-src", vec![
+                CspSource::Host("*".to_owned()),
+                CspSource::Scheme("data".to_owned()),
+            ]),
+        ]));
+        assert_eq!(csp.to_string(), "default-src 'self'; img-src * data:");
+        assert_eq!(csp.directive("IMG-SRC").unwrap().sources.len(), 2);
+
+        let r: Raw = "default-src 'self', img-src *".into();
+        assert!(ContentSecurityPolicy::parse_header(&r).is_err());
+        let r: Raw = " ; ".into();
+        assert!(ContentSecurityPolicy::parse_header(&r).is_err());
+    }
+
+    #[test]
+    fn test_sources() {
+        let r: Raw = "script-src 'nonce-r4nd0m' 'SHA384-abc+/=' 'strict-dynamic' \
+                      https: *.example.com 'report-sample'".into();
+        let csp: ContentSecurityPolicy = Header::parse_header(&r).unwrap();
+        let sources = csp.directive("script-src").unwrap().sources.clone();
+        assert_eq!(sources, vec![
+            CspSource::Nonce("r4nd0m".to_owned()),
+            CspSource::Hash(CspHashAlgo::Sha384, "abc+/=".to_owned()),
+            CspSource::StrictDynamic,
+            CspSource::Scheme("https".to_owned()),
+            CspSource::Host("*.example.com".to_owned()),
+            CspSource::Keyword("report-sample".to_owned()),
+        ]);
+
+        let directive = CspDirective::new("script-src", sources);
+        assert_eq!(directive.to_string(),
+                   "script-src 'nonce-r4nd0m' 'sha384-abc+/=' 'strict-dynamic' \
+                    https: *.example.com 'report-sample'");
+
+        let r: Raw = "script-src 'self".into();
+        assert!(ContentSecurityPolicy::parse_header(&r).is_err());
+        let r: Raw = "script-src 'nonce-a'b'".into();
+        assert!(ContentSecurityPolicy::parse_header(&r).is_err());
+        let r: Raw = "report-uri /csp-report".into();
+        let csp: ContentSecurityPolicy = Header::parse_header(&r).unwrap();
+        assert_eq!(csp.directive("report-uri").unwrap().sources,
+                   vec![CspSource::Host("/csp-report".to_owned())]);
+    }
+
+    #[test]
+    fn test_source_keywords() {
+        assert_eq!("'SELF'".parse::<CspSource>().unwrap(), CspSource::Self_);
+        assert_eq!("'none'".parse::<CspSource>().unwrap(), CspSource::None_);
+        assert_eq!("'unsafe-inline'".parse::<CspSource>().unwrap(), CspSource::UnsafeInline);
+        assert_eq!("'unsafe-eval'".parse::<CspSource>().unwrap(), CspSource::UnsafeEval);
+        assert_eq!("*".parse::<CspSource>().unwrap(), CspSource::Host("*".to_owned()));
+        assert_eq!("data:".parse::<CspSource>().unwrap(), CspSource::Scheme("data".to_owned()));
+        assert_eq!(CspSource::None_.to_string(), "'none'");
+        assert!("'".parse::<CspSource>().is_err());
+        assert!("".parse::<CspSource>().is_err());
+        assert!("'nonce-'".parse::<CspSource>().is_err());
+        assert!("'sha256-a b'".parse::<CspSource>().is_err());
+        assert_eq!("'unsafe-hashes'".parse::<CspSource>().unwrap(),
+                   CspSource::Keyword("unsafe-hashes".to_owned()));
+    }
+
+    #[test]
+    fn test_with_nonce() {
+        let r: Raw = "default-src 'self'; script-src 'self'".into();
+        let mut csp: ContentSecurityPolicy = Header::parse_header(&r).unwrap();
+        csp.with_nonce("Script-Src", "r4nd0m").unwrap();
+        csp.with_nonce("script-src", "r4nd0m").unwrap();
+        csp.with_nonce("style-src", "abc=").unwrap();
+        assert_eq!(csp.to_string(),
+                   "default-src 'self'; script-src 'self' 'nonce-r4nd0m'; \
+                    style-src 'nonce-abc='");
+    }
+
+    #[test]
+    fn test_with_nonce_invalid() {
+        let mut csp = ContentSecurityPolicy(vec![
+            CspDirective::new("script-src", vec![CspSource::Self_]),
+        ]);
+        assert!(csp.with_nonce("script-src", "x' 'unsafe-inline").is_err());
+        assert!(csp.with_nonce("script-src", "a b").is_err());
+        assert!(csp.with_nonce("script-src", "").is_err());
+        assert!(csp.with_nonce("script-src", "abc===").is_err());
+        assert!(csp.with_nonce("script-src", "a=b").is_err());
+        assert_eq!(csp.to_string(), "script-src 'self'");
+
+        let nonce = ContentSecurityPolicy::nonce_from_bytes(&[0xfb; 16]);
+        csp.with_nonce("script-src", &nonce).unwrap();
+        assert_eq!(csp.to_string(), format!("script-src 'self' 'nonce-{}'", nonce));
+    }
+}
+
+bench_header!(bench, ContentSecurityPolicy, { vec![b"default-src 'self'; img-src *".to_vec()] });
+
+standard_header!(ContentSecurityPolicy, CONTENT_SECURITY_POLICY);
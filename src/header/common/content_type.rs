@@ -1,4 +1,13 @@
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Write};
+use std::str;
+
+use base64;
+use header::{CacheControl, CacheDirective, Connection, Headers};
 use mime::{self, Mime};
+use percent_encoding::{percent_decode, percent_encode, PATH_SEGMENT_ENCODE_SET};
+#[allow(unused, deprecated)]
+use std::ascii::AsciiExt;
 
 header! {
     /// `Content-Type` header, defined in
@@ -16,6 +25,10 @@ header! {
     /// forces the use of Vec. This is to make sure the same header can't have more than 1 type. If
     /// this is an issue, it's possible to implement `Header` on a custom struct.
     ///
+    /// The media type, subtype and parameter names are case-insensitive, and
+    /// are normalized to lowercase when parsed, as is the `charset` value.
+    /// Other parameter values, such as a `boundary`, keep their case.
+    ///
     /// # ABNF
     ///
     /// ```text
@@ -56,9 +69,154 @@ header! {
             test1,
             vec![b"text/html"],
             Some(HeaderField(TEXT_HTML)));
+
+        #[test]
+        fn test_to_data_uri_base64() {
+            let png = b"\x89PNG\r\n\x1a\n";
+            assert_eq!(
+                HeaderField::png().to_data_uri(png, true),
+                "data:image/png;base64,iVBORw0KGgo=");
+        }
+
+        #[test]
+        fn test_to_data_uri_text() {
+            assert_eq!(
+                HeaderField::text_utf8().to_data_uri(b"Hello, 100% World!", false),
+                "data:text/plain;charset=utf-8,Hello,%20100%25%20World!");
+        }
+
+        #[test]
+        fn test_from_data_uri() {
+            let (ct, body) = HeaderField::from_data_uri(
+                "data:image/png;base64,iVBORw0KGgo=").unwrap();
+            assert_eq!(ct, HeaderField::png());
+            assert_eq!(body, b"\x89PNG\r\n\x1a\n");
+
+            let (ct, body) = HeaderField::from_data_uri(
+                "DATA:text/plain;charset=utf-8,Hello,%20100%25%20World!").unwrap();
+            assert_eq!(ct, HeaderField::text_utf8());
+            assert_eq!(body, b"Hello, 100% World!");
+
+            let (ct, body) = HeaderField::from_data_uri("data:,A%20brief%20note").unwrap();
+            assert_eq!(ct.to_string(), "text/plain; charset=us-ascii");
+            assert_eq!(body, b"A brief note");
+        }
+
+        #[test]
+        fn test_from_data_uri_invalid() {
+            assert!(HeaderField::from_data_uri("http://example.com/").is_err());
+            assert!(HeaderField::from_data_uri("data:text/plain").is_err());
+            assert!(HeaderField::from_data_uri("data:image/png;base64,!!!").is_err());
+            assert!(HeaderField::from_data_uri("data\u{20ac}xx,").is_err());
+            assert!(HeaderField::from_data_uri("data:\u{20ac}abcdef,x").is_err());
+        }
+
+        #[test]
+        fn test_case_normalization() {
+            let r: Raw = "TEXT/HTML; Charset=UTF-8; Boundary=AbC".into();
+            let ct = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(ct.to_string(), "text/html; charset=utf-8; boundary=AbC");
+            assert_eq!(ct.get_param(CHARSET), Some(UTF_8));
+            assert_eq!(ct.get_param("boundary").unwrap(), "AbC");
+
+            let r: Raw = "Multipart/Form-Data; BOUNDARY=X-Y_z".into();
+            let ct = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(ct.to_string(), "multipart/form-data; boundary=X-Y_z");
+            assert_eq!(ct.subtype(), mime::FORM_DATA);
+        }
+
+        #[test]
+        fn test_problem_details() {
+            let json = HeaderField::problem_json();
+            assert_eq!(json.to_string(), "application/problem+json");
+            assert!(json.is_problem_details());
+
+            let r: Raw = "Application/Problem+XML; charset=utf-8".into();
+            let xml = HeaderField::parse_header(&r).unwrap();
+            assert!(xml.is_problem_details());
+
+            assert!(!HeaderField::json().is_problem_details());
+            assert!(!HeaderField::xml().is_problem_details());
+            let r: Raw = "application/problem+yaml".into();
+            assert!(!HeaderField::parse_header(&r).unwrap().is_problem_details());
+        }
+
+        #[test]
+        fn test_multipart_boundary() {
+            let r: Raw = "multipart/form-data; boundary=----WebKitFormBoundary7MA4YWxkTrZu0gW".into();
+            let ct = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(ct.multipart_boundary(), Ok("----WebKitFormBoundary7MA4YWxkTrZu0gW"));
+
+            let r: Raw = "multipart/mixed; boundary=\"simple boundary\"".into();
+            let ct = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(ct.multipart_boundary(), Ok("simple boundary"));
+        }
+
+        #[test]
+        fn test_multipart_boundary_invalid() {
+            fn boundary(s: &str) -> Result<String, BoundaryError> {
+                let r: Raw = s.into();
+                let ct = HeaderField::parse_header(&r).unwrap();
+                ct.multipart_boundary().map(|b| b.to_owned())
+            }
+
+            assert_eq!(boundary("multipart/form-data"), Err(BoundaryError::Missing));
+            assert_eq!(boundary("text/plain; boundary=abc"), Err(BoundaryError::NotMultipart));
+            let long = format!("multipart/mixed; boundary={}", "a".repeat(71));
+            assert_eq!(boundary(&long), Err(BoundaryError::InvalidLength));
+            let max = format!("multipart/mixed; boundary={}", "a".repeat(70));
+            assert!(boundary(&max).is_ok());
+            assert_eq!(boundary("multipart/mixed; boundary=a*b"),
+                       Err(BoundaryError::InvalidCharacter));
+            assert_eq!(boundary("multipart/mixed; boundary=\"ends \""),
+                       Err(BoundaryError::TrailingSpace));
+        }
+
+        #[test]
+        fn test_body_format() {
+            fn format(s: &str) -> BodyFormat {
+                let r: Raw = s.into();
+                HeaderField::parse_header(&r).unwrap().body_format()
+            }
+
+            assert_eq!(format("application/json"), BodyFormat::Json);
+            assert_eq!(format("application/vnd.api+json"), BodyFormat::Json);
+            assert_eq!(format("Application/JSON; charset=utf-8"), BodyFormat::Json);
+            assert_eq!(format("application/cbor"), BodyFormat::Cbor);
+            assert_eq!(format("application/senml+cbor"), BodyFormat::Cbor);
+            assert_eq!(format("application/x-www-form-urlencoded"), BodyFormat::FormUrlencoded);
+            assert_eq!(format("multipart/form-data; boundary=abc"), BodyFormat::Multipart);
+            assert_eq!(format("text/xml"), BodyFormat::Xml);
+            assert_eq!(format("image/svg+xml"), BodyFormat::Xml);
+            assert_eq!(format("text/plain"), BodyFormat::Text);
+            assert_eq!(format("application/octet-stream"), BodyFormat::Other);
+        }
+
+        #[test]
+        fn test_event_stream() {
+            let sse = HeaderField::event_stream();
+            assert_eq!(sse.to_string(), "text/event-stream");
+            assert!(sse.is_event_stream());
+
+            let r: Raw = "Text/Event-Stream; charset=utf-8".into();
+            assert!(HeaderField::parse_header(&r).unwrap().is_event_stream());
+            assert!(!HeaderField::text().is_event_stream());
+            assert!(!HeaderField::octet_stream().is_event_stream());
+        }
+
+        #[test]
+        fn test_event_stream_headers() {
+            let headers = HeaderField::event_stream_headers();
+            assert_eq!(headers.len(), 3);
+            assert!(headers.get::<HeaderField>().unwrap().is_event_stream());
+            assert_eq!(headers.get(), Some(&CacheControl(vec![CacheDirective::NoCache])));
+            assert!(headers.get::<Connection>().unwrap().contains_keep_alive());
+        }
     }
 }
 
+__hyper__from_str!(ContentType);
+
 impl ContentType {
     /// A constructor  to easily create a `Content-Type: application/json` header.
     #[inline]
@@ -120,8 +278,271 @@ impl ContentType {
     pub fn octet_stream() -> ContentType {
         ContentType(mime::APPLICATION_OCTET_STREAM)
     }
+
+    /// A constructor  to easily create a `Content-Type: application/problem+json` header,
+    /// for problem details as defined in [RFC7807](https://tools.ietf.org/html/rfc7807).
+    #[inline]
+    pub fn problem_json() -> ContentType {
+        ContentType("application/problem+json".parse().unwrap())
+    }
+
+    /// Returns true if this is an RFC7807 problem details media type, either
+    /// `application/problem+json` or `application/problem+xml`.
+    pub fn is_problem_details(&self) -> bool {
+        self.type_() == mime::APPLICATION && self.subtype() == "problem" &&
+            self.suffix().map_or(false, |suffix| suffix == mime::JSON || suffix == mime::XML)
+    }
+
+    /// A constructor  to easily create a `Content-Type: text/event-stream` header,
+    /// for [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html).
+    ///
+    /// An event stream response is long-lived and should not be cached, so it
+    /// is usually sent along with `Cache-Control: no-cache` and
+    /// `Connection: keep-alive`. See `event_stream_headers`.
+    #[inline]
+    pub fn event_stream() -> ContentType {
+        ContentType("text/event-stream".parse().unwrap())
+    }
+
+    /// Returns true if this is the `text/event-stream` media type.
+    pub fn is_event_stream(&self) -> bool {
+        self.type_() == mime::TEXT && self.subtype() == "event-stream"
+    }
+
+    /// The recommended headers of a Server-Sent Events response:
+    /// `Content-Type: text/event-stream`, `Cache-Control: no-cache` and
+    /// `Connection: keep-alive`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{ContentType, Headers};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.extend(ContentType::event_stream_headers().iter());
+    /// assert!(headers.get::<ContentType>().unwrap().is_event_stream());
+    /// ```
+    pub fn event_stream_headers() -> Headers {
+        let mut headers = Headers::new();
+        headers.set(ContentType::event_stream());
+        headers.set(CacheControl(vec![CacheDirective::NoCache]));
+        headers.set(Connection::keep_alive());
+        headers
+    }
+
+    /// Build a `data:` URI, as defined in
+    /// [RFC2397](https://tools.ietf.org/html/rfc2397), pairing this media
+    /// type with the given body.
+    ///
+    /// If `base64` is true the body is base64 encoded, otherwise it is
+    /// percent encoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::ContentType;
+    ///
+    /// assert_eq!(
+    ///     ContentType::text_utf8().to_data_uri(b"Hi there", false),
+    ///     "data:text/plain;charset=utf-8,Hi%20there");
+    /// ```
+    pub fn to_data_uri(&self, body: &[u8], base64: bool) -> String {
+        let mut uri = String::from("data:");
+        let mime: &str = self.0.as_ref();
+        uri.push_str(mime.split(';').next().unwrap_or("").trim());
+        for (name, value) in self.0.params() {
+            let _ = write!(uri, ";{}={}", name, value);
+        }
+        if base64 {
+            uri.push_str(";base64,");
+            uri.push_str(&base64::encode(body));
+        } else {
+            uri.push(',');
+            let _ = write!(uri, "{}", percent_encode(body, PATH_SEGMENT_ENCODE_SET));
+        }
+        uri
+    }
+
+    /// Parse a `data:` URI, as defined in
+    /// [RFC2397](https://tools.ietf.org/html/rfc2397), into its media type
+    /// and decoded body.
+    ///
+    /// Per the RFC, the media type defaults to
+    /// `text/plain; charset=US-ASCII` if omitted.
+    pub fn from_data_uri(uri: &str) -> ::Result<(ContentType, Vec<u8>)> {
+        if uri.len() < 5 || !uri.as_bytes()[..5].eq_ignore_ascii_case(b"data:") {
+            return Err(::Error::Header);
+        }
+        let mut parts = uri[5..].splitn(2, ',');
+        let (mut meta, data) = match (parts.next(), parts.next()) {
+            (Some(meta), Some(data)) => (meta, data),
+            _ => return Err(::Error::Header),
+        };
+
+        let base64 = meta.len() >= 7 &&
+            meta.as_bytes()[meta.len() - 7..].eq_ignore_ascii_case(b";base64");
+        if base64 {
+            meta = &meta[..meta.len() - 7];
+        }
+
+        let mime = if meta.is_empty() {
+            "text/plain; charset=US-ASCII".parse()
+        } else if meta.starts_with(';') {
+            format!("text/plain{}", meta).parse()
+        } else {
+            meta.parse()
+        };
+        let mime: Mime = try!(mime.map_err(|_| ::Error::Header));
+
+        let body: Vec<u8> = percent_decode(data.as_bytes()).collect();
+        let body = if base64 {
+            try!(base64::decode(&body).map_err(|_| ::Error::Header))
+        } else {
+            body
+        };
+        Ok((ContentType(mime), body))
+    }
+
+    /// Get the `boundary` parameter of a received `multipart/*` media type,
+    /// validated as defined in
+    /// [RFC2046](https://tools.ietf.org/html/rfc2046#section-5.1.1).
+    ///
+    /// A boundary must be 1 to 70 characters long, of digits, letters, space
+    /// and `'()+_,-./:=?`, and must not end with a space. A multipart parser
+    /// should reject the body if this returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{BoundaryError, ContentType};
+    ///
+    /// let ct = ContentType("multipart/mixed; boundary=\"gc0p4Jq0M2Yt08j34c0p\"".parse().unwrap());
+    /// assert_eq!(ct.multipart_boundary(), Ok("gc0p4Jq0M2Yt08j34c0p"));
+    ///
+    /// assert_eq!(ContentType::json().multipart_boundary(), Err(BoundaryError::NotMultipart));
+    /// ```
+    pub fn multipart_boundary(&self) -> Result<&str, BoundaryError> {
+        if self.type_() != mime::MULTIPART {
+            return Err(BoundaryError::NotMultipart);
+        }
+        let boundary = match self.get_param(mime::BOUNDARY) {
+            Some(boundary) => boundary.as_str(),
+            None => return Err(BoundaryError::Missing),
+        };
+        if boundary.is_empty() || boundary.len() > 70 {
+            return Err(BoundaryError::InvalidLength);
+        }
+        if !boundary.bytes().all(is_bchar) {
+            return Err(BoundaryError::InvalidCharacter);
+        }
+        if boundary.ends_with(' ') {
+            return Err(BoundaryError::TrailingSpace);
+        }
+        Ok(boundary)
+    }
+
+    /// Classify the media type by the format of the body, for dispatching
+    /// to a parser.
+    ///
+    /// Structured syntax suffixes are recognized, so that for example
+    /// `application/vnd.api+json` is `Json`, and `image/svg+xml` is `Xml`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{BodyFormat, ContentType};
+    ///
+    /// assert_eq!(ContentType::json().body_format(), BodyFormat::Json);
+    /// assert_eq!(ContentType::form_url_encoded().body_format(), BodyFormat::FormUrlencoded);
+    /// assert_eq!(ContentType::png().body_format(), BodyFormat::Other);
+    /// ```
+    pub fn body_format(&self) -> BodyFormat {
+        let suffix = self.suffix();
+        let suffix = suffix.as_ref().map(|s| s.as_str());
+        if self.type_() == mime::MULTIPART {
+            BodyFormat::Multipart
+        } else if suffix == Some("json") ||
+            (self.type_() == mime::APPLICATION && self.subtype() == mime::JSON)
+        {
+            BodyFormat::Json
+        } else if suffix == Some("cbor") ||
+            (self.type_() == mime::APPLICATION && self.subtype() == "cbor")
+        {
+            BodyFormat::Cbor
+        } else if self.type_() == mime::APPLICATION &&
+            self.subtype() == mime::WWW_FORM_URLENCODED
+        {
+            BodyFormat::FormUrlencoded
+        } else if suffix == Some("xml") ||
+            ((self.type_() == mime::APPLICATION || self.type_() == mime::TEXT) &&
+             self.subtype() == mime::XML)
+        {
+            BodyFormat::Xml
+        } else if self.type_() == mime::TEXT {
+            BodyFormat::Text
+        } else {
+            BodyFormat::Other
+        }
+    }
 }
 
+/// The format of a body, as classified by `ContentType::body_format`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum BodyFormat {
+    /// `application/json`, or a `+json` suffix.
+    Json,
+    /// `application/cbor`, or a `+cbor` suffix.
+    Cbor,
+    /// `application/x-www-form-urlencoded`.
+    FormUrlencoded,
+    /// `multipart/*`.
+    Multipart,
+    /// `application/xml`, `text/xml`, or a `+xml` suffix.
+    Xml,
+    /// Any other `text/*` type.
+    Text,
+    /// Any other type.
+    Other,
+}
+
+fn is_bchar(b: u8) -> bool {
+    match b {
+        b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' => true,
+        b'\'' | b'(' | b')' | b'+' | b'_' | b',' | b'-' | b'.' | b'/' | b':' | b'=' | b'?' | b' ' => true,
+        _ => false,
+    }
+}
+
+/// An invalid `multipart/*` boundary, as returned by
+/// `ContentType::multipart_boundary`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BoundaryError {
+    /// The media type is not `multipart/*`.
+    NotMultipart,
+    /// The `boundary` parameter is absent.
+    Missing,
+    /// The boundary is empty or longer than 70 characters.
+    InvalidLength,
+    /// The boundary contains a character not allowed by RFC2046.
+    InvalidCharacter,
+    /// The boundary ends with a space.
+    TrailingSpace,
+}
+
+impl Display for BoundaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            BoundaryError::NotMultipart => "media type is not multipart",
+            BoundaryError::Missing => "multipart boundary parameter missing",
+            BoundaryError::InvalidLength => "multipart boundary is not 1 to 70 characters long",
+            BoundaryError::InvalidCharacter => "multipart boundary contains an invalid character",
+            BoundaryError::TrailingSpace => "multipart boundary ends with a space",
+        })
+    }
+}
+
+impl StdError for BoundaryError {}
+
 impl Eq for ContentType {}
 
 bench_header!(bench, ContentType, { vec![b"application/json".to_vec()] });
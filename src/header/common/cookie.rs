@@ -143,6 +143,8 @@ impl Header for Cookie {
     }
 }
 
+__hyper__from_str!(Cookie);
+
 impl PartialEq for Cookie {
     fn eq(&self, other: &Cookie) -> bool {
         if self.0.len() == other.0.len() {
@@ -1,4 +1,4 @@
-use header::EntityTag;
+use header::{EntityTag, Headers, Vary};
 
 header! {
     /// `ETag` header, defined in [RFC7232](http://tools.ietf.org/html/rfc7232#section-2.3)
@@ -89,7 +89,90 @@ header! {
         test_header!(test15,
             vec![b"\""],
             None::<ETag>);
+
+        #[test]
+        fn test_variant_etag() {
+            let base = EntityTag::strong("abc".to_owned());
+            let vary: Vary = "Accept-Encoding".parse().unwrap();
+
+            let mut gzip = Headers::new();
+            gzip.set_raw("Accept-Encoding", "gzip");
+            let mut br = Headers::new();
+            br.set_raw("accept-encoding", "br");
+
+            let gzip_etag = variant_etag(&base, &vary, &gzip);
+            let br_etag = variant_etag(&base, &vary, &br);
+            assert!(gzip_etag.strong_ne(&br_etag));
+            assert!(gzip_etag.tag().starts_with("abc-"));
+            assert!(!gzip_etag.weak);
+            assert_eq!(variant_etag(&base, &vary, &gzip), gzip_etag);
+            assert!(variant_etag(&base, &vary, &Headers::new()).strong_ne(&gzip_etag));
+
+            // headers not listed in Vary do not matter
+            gzip.set_raw("Accept-Language", "en");
+            assert_eq!(variant_etag(&base, &vary, &gzip), gzip_etag);
+
+            let weak = EntityTag::weak("abc".to_owned());
+            assert!(variant_etag(&weak, &vary, &br).weak);
+            assert_eq!(variant_etag(&base, &Vary::Any, &br), base);
+        }
+    }
+}
+
+__hyper__from_str!(ETag);
+
+/// Derive the entity-tag of a negotiated representation, from the `base`
+/// entity-tag of the resource and the request header fields selected by
+/// `vary`.
+///
+/// Representations negotiated on different request header values get
+/// distinct entity-tags, so that a cache does not validate one variant with
+/// the entity-tag of another. The request values are hashed, in `Vary`
+/// order, into a suffix of the base tag. The hash is stable across builds,
+/// and absent fields are distinguished from present ones. Weakness is kept.
+///
+/// `Vary: *` cannot select request fields, so then `base` is returned as
+/// is.
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{variant_etag, EntityTag, Headers, Vary};
+///
+/// let base = EntityTag::strong("abc".to_owned());
+/// let vary: Vary = "Accept-Encoding".parse().unwrap();
+/// let mut req = Headers::new();
+/// req.set_raw("Accept-Encoding", "gzip");
+/// let etag = variant_etag(&base, &vary, &req);
+/// assert!(etag.tag().starts_with("abc-"));
+/// ```
+pub fn variant_etag(base: &EntityTag, vary: &Vary, req: &Headers) -> EntityTag {
+    let names = match *vary {
+        Vary::Any => return base.clone(),
+        Vary::Items(ref names) => names,
+    };
+    // 64-bit FNV-1a, which unlike `DefaultHasher` is stable across releases
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    {
+        let mut write = |bytes: &[u8]| {
+            for &b in bytes {
+                hash ^= u64::from(b);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        for name in names {
+            write(name.to_ascii_lowercase().as_bytes());
+            match req.get_raw(name) {
+                Some(raw) => for line in raw.iter() {
+                    write(b"\n");
+                    write(line);
+                },
+                None => write(b"\r"),
+            }
+            write(b"\0");
+        }
     }
+    EntityTag::new(base.weak, format!("{}-{:016x}", base.tag(), hash))
 }
 
 bench_header!(bench, ETag, { vec![b"W/\"nonemptytag\"".to_vec()] });
@@ -20,7 +20,7 @@ use header::{Header, RawLike};
 /// let mut headers = Headers::new();
 /// headers.set(Expect::Continue);
 /// ```
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
 pub enum Expect {
     /// The value `100-continue`.
     Continue
@@ -59,6 +59,8 @@ impl Header for Expect {
     }
 }
 
+__hyper__from_str!(Expect);
+
 impl fmt::Display for Expect {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str("100-continue")
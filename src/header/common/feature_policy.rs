@@ -0,0 +1,170 @@
+use std::fmt;
+
+use header::{AllowlistEntry, Header, PermissionsPolicy, PolicyDirective, RawLike, parsing};
+
+/// `Feature-Policy` header, defined in
+/// [Feature Policy](https://w3c.github.io/webappsec-feature-policy/#feature-policy-http-header-field)
+///
+/// The legacy `Feature-Policy` response header field controls which origins
+/// may use browser features in the document and its frames. It has been
+/// superseded by `Permissions-Policy`, to and from which it converts with
+/// `From`.
+///
+/// Each feature is followed by its allowlist of origins, which may also
+/// include the `'self'` and `'src'` keywords, or `*` for all origins.
+/// `'none'` stands for an empty allowlist.
+///
+/// # ABNF
+///
+/// ```text
+/// serialized-feature-policy = serialized-policy-directive
+///                             *(";" serialized-policy-directive)
+/// serialized-policy-directive = feature-identifier RWS allow-list
+/// allow-list = allow-list-value *(RWS allow-list-value)
+/// allow-list-value = serialized-origin / "*" / "'self'" / "'src'" / "'none'"
+/// ```
+///
+/// # Example values
+///
+/// * `geolocation 'self' https://example.com; camera 'none'`
+/// * `fullscreen *`
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{Headers, FeaturePolicy, PolicyDirective, AllowlistEntry};
+///
+/// let mut headers = Headers::new();
+/// headers.set(FeaturePolicy(vec![
+///     PolicyDirective::new("geolocation", vec![AllowlistEntry::Self_]),
+///     PolicyDirective::new("camera", vec![]),
+/// ]));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct FeaturePolicy(pub Vec<PolicyDirective>);
+
+__hyper__deref!(FeaturePolicy => Vec<PolicyDirective>);
+
+impl From<PermissionsPolicy> for FeaturePolicy {
+    fn from(policy: PermissionsPolicy) -> FeaturePolicy {
+        FeaturePolicy(policy.0)
+    }
+}
+
+impl Header for FeaturePolicy {
+    fn header_name() -> &'static str {
+        static NAME: &'static str = "Feature-Policy";
+        NAME
+    }
+
+    fn parse_header<'a, T>(raw: &'a T) -> ::Result<FeaturePolicy>
+    where T: RawLike<'a>
+    {
+        parsing::from_one_raw_str(raw)
+            .and_then(|s: String| {
+                let directives = s.split(';')
+                    .filter_map(|d| {
+                        let mut tokens = d.split_whitespace();
+                        tokens.next().map(|feature| {
+                            let allowlist = tokens
+                                .filter(|t| !t.eq_ignore_ascii_case("'none'"))
+                                .map(parse_allowlist_value)
+                                .collect();
+                            PolicyDirective::new(feature.to_ascii_lowercase(), allowlist)
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                if directives.is_empty() {
+                    Err(::Error::Header)
+                } else {
+                    Ok(FeaturePolicy(directives))
+                }
+            })
+    }
+
+    fn fmt_header(&self, f: &mut ::header::Formatter) -> fmt::Result {
+        f.fmt_line(self)
+    }
+}
+
+__hyper__from_str!(FeaturePolicy);
+
+fn parse_allowlist_value(value: &str) -> AllowlistEntry {
+    if value == "*" {
+        AllowlistEntry::Any
+    } else if value.eq_ignore_ascii_case("'self'") {
+        AllowlistEntry::Self_
+    } else if value.eq_ignore_ascii_case("'src'") {
+        AllowlistEntry::Src
+    } else {
+        AllowlistEntry::Origin(value.to_owned())
+    }
+}
+
+impl fmt::Display for FeaturePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, directive) in self.iter().enumerate() {
+            if i > 0 {
+                try!(f.write_str("; "));
+            }
+            try!(f.write_str(&directive.feature));
+            if directive.allowlist.is_empty() {
+                try!(f.write_str(" 'none'"));
+            }
+            for entry in &directive.allowlist {
+                try!(write!(f, " {}", match *entry {
+                    AllowlistEntry::Any => "*",
+                    AllowlistEntry::Self_ => "'self'",
+                    AllowlistEntry::Src => "'src'",
+                    AllowlistEntry::Origin(ref origin) => origin,
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeaturePolicy;
+    use header::{AllowlistEntry, Header, PermissionsPolicy, PolicyDirective, Raw};
+
+    #[test]
+    fn test_parse() {
+        let r: Raw = "geolocation 'self' https://x.com;  camera 'none' ;".into();
+        let policy: FeaturePolicy = Header::parse_header(&r).unwrap();
+        assert_eq!(policy, FeaturePolicy(vec![
+            PolicyDirective::new("geolocation", vec![
+                AllowlistEntry::Self_,
+                AllowlistEntry::Origin("https://x.com".to_owned()),
+            ]),
+            PolicyDirective::new("camera", vec![]),
+        ]));
+        assert_eq!(policy.to_string(), "geolocation 'self' https://x.com; camera 'none'");
+
+        let r: Raw = " ; ".into();
+        assert!(FeaturePolicy::parse_header(&r).is_err());
+    }
+
+    #[test]
+    fn test_special_values() {
+        let r: Raw = "fullscreen *; payment 'NONE'; usb 'src'".into();
+        let policy: FeaturePolicy = Header::parse_header(&r).unwrap();
+        assert_eq!(policy, FeaturePolicy(vec![
+            PolicyDirective::new("fullscreen", vec![AllowlistEntry::Any]),
+            PolicyDirective::new("payment", vec![]),
+            PolicyDirective::new("usb", vec![AllowlistEntry::Src]),
+        ]));
+        assert_eq!(policy.to_string(), "fullscreen *; payment 'none'; usb 'src'");
+    }
+
+    #[test]
+    fn test_from_permissions_policy() {
+        let policy: PermissionsPolicy = "camera=(self), fullscreen=*".parse().unwrap();
+        let policy = FeaturePolicy::from(policy);
+        assert_eq!(policy.to_string(), "camera 'self'; fullscreen *");
+        assert_eq!(PermissionsPolicy::from(policy).to_string(), "camera=(self), fullscreen=*");
+    }
+}
+
+bench_header!(bench, FeaturePolicy, { vec![b"geolocation 'self' https://x.com; camera 'none'".to_vec()] });
@@ -0,0 +1,470 @@
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+use unicase;
+use header::{Header, Headers, Raw, RawLike};
+use header::{ForwardedNode, XForwardedFor, XForwardedHost, XForwardedProto};
+use header::parsing::{comma_delimited_iter, fmt_comma_delimited, fmt_token_or_quoted, is_token,
+                      split_unquoted, unescape};
+
+/// `Forwarded` header, defined in [RFC7239](https://tools.ietf.org/html/rfc7239)
+///
+/// The `Forwarded` header field allows proxy components to disclose
+/// information lost in the proxying process, such as the original client
+/// address, the host requested by the client and the protocol used. Each
+/// proxy appends one element to the list.
+///
+/// It standardizes the de-facto `X-Forwarded-For`, `X-Forwarded-Host` and
+/// `X-Forwarded-Proto` headers (`XForwardedFor`, `XForwardedHost` and
+/// `XForwardedProto`); see `Forwarded::from_x_forwarded` and
+/// `Forwarded::set_x_forwarded` for converting between the two.
+///
+/// # ABNF
+///
+/// ```text
+/// Forwarded         = 1#forwarded-element
+/// forwarded-element = [ forwarded-pair ] *( ";" [ forwarded-pair ] )
+/// forwarded-pair    = token "=" value
+/// value             = token / quoted-string
+/// ```
+///
+/// # Example values
+/// * `for="_gazonk"`
+/// * `For="[2001:db8:cafe::17]:4711"`
+/// * `for=192.0.2.60;proto=http;by=203.0.113.43`
+/// * `for=192.0.2.43, for=198.51.100.17`
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{Headers, Forwarded, ForwardedElement};
+///
+/// let mut headers = Headers::new();
+/// headers.set(
+///     Forwarded(vec![ForwardedElement {
+///         for_: Some("192.0.2.60".to_owned()),
+///         proto: Some("http".to_owned()),
+///         by: Some("203.0.113.43".to_owned()),
+///         host: None,
+///     }])
+/// );
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Forwarded(pub Vec<ForwardedElement>);
+
+__hyper__deref!(Forwarded => Vec<ForwardedElement>);
+
+/// A single element of a `Forwarded` header, added by one proxy.
+///
+/// Node identifiers (`for` and `by`) are kept as written, e.g.
+/// `192.0.2.43:47011`, `[2001:db8:cafe::17]` or an obfuscated `_hidden`.
+/// Extension parameters are ignored.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct ForwardedElement {
+    /// The `by` parameter, the interface where the request came in to the
+    /// proxy.
+    pub by: Option<String>,
+    /// The `for` parameter, the node making the request to the proxy.
+    pub for_: Option<String>,
+    /// The `host` parameter, the `Host` header as received by the proxy.
+    pub host: Option<String>,
+    /// The `proto` parameter, the protocol used to make the request.
+    pub proto: Option<String>,
+}
+
+impl Forwarded {
+    /// Synthesize a `Forwarded` header from the legacy `X-Forwarded-For`,
+    /// `X-Forwarded-Host` and `X-Forwarded-Proto` headers.
+    ///
+    /// Each address of `X-Forwarded-For` becomes the `for` of one element,
+    /// IPv6 addresses being bracketed as required. The host and protocol
+    /// describe the request of the original client, so are given to the
+    /// first element. Returns `None` if none of the headers is present.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, Forwarded};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set_raw("X-Forwarded-For", "192.0.2.43, 2001:db8::17");
+    /// headers.set_raw("X-Forwarded-Proto", "https");
+    ///
+    /// let forwarded = Forwarded::from_x_forwarded(&headers).unwrap();
+    /// assert_eq!(forwarded.to_string(),
+    ///            "for=192.0.2.43;proto=https, for=\"[2001:db8::17]\"");
+    /// ```
+    pub fn from_x_forwarded(headers: &Headers) -> Option<Forwarded> {
+        let host = headers.get::<XForwardedHost>().map(|host| host.to_string());
+        let proto = headers.get::<XForwardedProto>().map(|proto| proto.to_string());
+
+        let mut elements = headers.get::<XForwardedFor>()
+            .map_or(&[][..], |xff| &xff[..])
+            .iter()
+            .map(|node| ForwardedElement {
+                for_: Some(match *node {
+                    ForwardedNode::Ip(IpAddr::V6(ref ip)) => format!("[{}]", ip),
+                    ref node => node.to_string(),
+                }),
+                ..ForwardedElement::default()
+            })
+            .collect::<Vec<_>>();
+        if elements.is_empty() {
+            if host.is_none() && proto.is_none() {
+                return None;
+            }
+            elements.push(ForwardedElement::default());
+        }
+        elements[0].host = host;
+        elements[0].proto = proto;
+        Some(Forwarded(elements))
+    }
+
+    /// Set the legacy `X-Forwarded-For`, `X-Forwarded-Host` and
+    /// `X-Forwarded-Proto` headers from this `Forwarded` header.
+    ///
+    /// `X-Forwarded-For` lists the address of every element's `for`, with
+    /// brackets and ports removed, using `unknown` for elements without one.
+    /// The host and protocol are taken from the first element that has them.
+    /// Headers with no corresponding value are left untouched.
+    pub fn set_x_forwarded(&self, headers: &mut Headers) {
+        if self.iter().any(|e| e.for_.is_some()) {
+            let nodes = self.iter()
+                .map(|e| match e.for_ {
+                    Some(ref node) => strip_port(node).parse().unwrap_or(ForwardedNode::Unknown),
+                    None => ForwardedNode::Unknown,
+                })
+                .collect();
+            headers.set(XForwardedFor(nodes));
+        }
+        if let Some(host) = self.iter().filter_map(|e| e.host.clone()).next() {
+            headers.set(XForwardedHost::new(host));
+        }
+        if let Some(ref proto) = self.iter().filter_map(|e| e.proto.clone()).next() {
+            if let Ok(proto) = XForwardedProto::parse_header(&Raw::from(&proto[..])) {
+                headers.set(proto);
+            }
+        }
+    }
+}
+
+/// Strip the port and IPv6 brackets from a `node`.
+fn strip_port(node: &str) -> &str {
+    if node.starts_with('[') {
+        match node.find(']') {
+            Some(end) => &node[1..end],
+            None => node,
+        }
+    } else {
+        node.split(':').next().unwrap_or(node)
+    }
+}
+
+impl Header for Forwarded {
+    fn header_name() -> &'static str {
+        static NAME: &'static str = "Forwarded";
+        NAME
+    }
+
+    fn parse_header<'a, T>(raw: &'a T) -> ::Result<Forwarded>
+    where T: RawLike<'a>
+    {
+        let mut elements = Vec::new();
+        for element in comma_delimited_iter(raw) {
+            elements.push(try!(try!(element).parse()));
+        }
+        if !elements.is_empty() {
+            Ok(Forwarded(elements))
+        } else {
+            Err(::Error::Header)
+        }
+    }
+
+    fn fmt_header(&self, f: &mut ::header::Formatter) -> fmt::Result {
+        f.fmt_line(self)
+    }
+}
+
+__hyper__from_str!(Forwarded);
+
+impl fmt::Display for Forwarded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_comma_delimited(f, &self[..])
+    }
+}
+
+impl fmt::Display for ForwardedElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pairs = [
+            ("for", &self.for_),
+            ("by", &self.by),
+            ("host", &self.host),
+            ("proto", &self.proto),
+        ];
+        let mut first = true;
+        for &(name, value) in &pairs {
+            if let Some(ref value) = *value {
+                if !first {
+                    try!(f.write_str(";"));
+                }
+                first = false;
+                try!(write!(f, "{}=", name));
+                try!(fmt_token_or_quoted(f, value));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check a `for` or `by` value against the `node` grammar of RFC 7239:
+///
+/// ```text
+/// node     = nodename [ ":" node-port ]
+/// nodename = IPv4address / "[" IPv6address "]" / "unknown" / obfnode
+/// obfnode  = "_" 1*( ALPHA / DIGIT / "." / "_" / "-")
+/// node-port = port / obfport
+/// port     = 1*5DIGIT
+/// obfport  = "_" 1*(ALPHA / DIGIT / "." / "_" / "-")
+/// ```
+///
+/// An `IPvFuture` literal is accepted in the brackets as well.
+fn is_valid_node(node: &str) -> bool {
+    let (name, port) = if node.starts_with('[') {
+        match node.find(']') {
+            Some(end) => (&node[..end + 1], &node[end + 1..]),
+            None => return false,
+        }
+    } else {
+        match node.find(':') {
+            Some(idx) => (&node[..idx], &node[idx..]),
+            None => (node, ""),
+        }
+    };
+    let valid_name = if name.starts_with('[') {
+        let literal = &name[1..name.len() - 1];
+        literal.parse::<Ipv6Addr>().is_ok() || is_ipv_future(literal)
+    } else {
+        name.parse::<Ipv4Addr>().is_ok() || unicase::eq_ascii(name, "unknown") ||
+            is_obfuscated(name)
+    };
+    let valid_port = port.is_empty() || port.starts_with(':') && {
+        let port = &port[1..];
+        (!port.is_empty() && port.len() <= 5 && port.bytes().all(|b| b.is_ascii_digit())) ||
+            is_obfuscated(port)
+    };
+    valid_name && valid_port
+}
+
+/// `"_" 1*( ALPHA / DIGIT / "." / "_" / "-" )`
+fn is_obfuscated(s: &str) -> bool {
+    s.len() > 1 && s.starts_with('_') &&
+        s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'.' || b == b'_' || b == b'-')
+}
+
+/// `"v" 1*HEXDIG "." 1*( unreserved / sub-delims / ":" )`
+fn is_ipv_future(s: &str) -> bool {
+    if !(s.starts_with('v') || s.starts_with('V')) {
+        return false;
+    }
+    let mut parts = s[1..].splitn(2, '.');
+    let version = parts.next().unwrap_or("");
+    let address = parts.next().unwrap_or("");
+    !version.is_empty() && version.bytes().all(|b| b.is_ascii_hexdigit()) &&
+        !address.is_empty() && address.bytes().all(|b| {
+            b.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:".contains(&b)
+        })
+}
+
+/// Unquote a `token / quoted-string` value.
+fn parse_value(s: &str) -> Option<String> {
+    if !s.starts_with('"') {
+        return if is_token(s) {
+            Some(s.to_owned())
+        } else {
+            None
+        };
+    }
+    if s.len() < 2 || !s.ends_with('"') {
+        return None;
+    }
+    Some(unescape(&s[1..s.len() - 1]))
+}
+
+impl FromStr for ForwardedElement {
+    type Err = ::Error;
+    fn from_str(s: &str) -> ::Result<ForwardedElement> {
+        let mut element = ForwardedElement::default();
+        for pair in split_unquoted(s, b';') {
+            let pair = pair.trim();
+            if pair.is_empty() {
+                continue;
+            }
+            let mut parts = pair.splitn(2, '=');
+            let name = parts.next().unwrap_or("");
+            let value = match parts.next().and_then(parse_value) {
+                Some(value) => value,
+                None => return Err(::Error::Header),
+            };
+            let is_node = unicase::eq_ascii(name, "for") || unicase::eq_ascii(name, "by");
+            if is_node && !is_valid_node(&value) {
+                return Err(::Error::Header);
+            }
+            let field = if unicase::eq_ascii(name, "for") {
+                &mut element.for_
+            } else if unicase::eq_ascii(name, "by") {
+                &mut element.by
+            } else if unicase::eq_ascii(name, "host") {
+                &mut element.host
+            } else if unicase::eq_ascii(name, "proto") {
+                &mut element.proto
+            } else {
+                continue;
+            };
+            // Each parameter must not occur more than once per element.
+            if field.is_some() {
+                return Err(::Error::Header);
+            }
+            *field = Some(value);
+        }
+        Ok(element)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use header::{Header, Headers, Raw};
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let r: Raw = "For=\"[2001:db8:cafe::17]:4711\"".into();
+        let forwarded: Forwarded = Header::parse_header(&r).unwrap();
+        assert_eq!(forwarded.0, vec![ForwardedElement {
+            for_: Some("[2001:db8:cafe::17]:4711".to_owned()),
+            ..ForwardedElement::default()
+        }]);
+
+        let r: Raw = "for=192.0.2.60;proto=http;by=203.0.113.43, for=_hidden;ext=1".into();
+        let forwarded: Forwarded = Header::parse_header(&r).unwrap();
+        assert_eq!(forwarded.0, vec![
+            ForwardedElement {
+                for_: Some("192.0.2.60".to_owned()),
+                by: Some("203.0.113.43".to_owned()),
+                proto: Some("http".to_owned()),
+                host: None,
+            },
+            ForwardedElement {
+                for_: Some("_hidden".to_owned()),
+                ..ForwardedElement::default()
+            },
+        ]);
+        assert_eq!(forwarded.to_string(),
+                   "for=192.0.2.60;by=203.0.113.43;proto=http, for=_hidden");
+
+        let r: Raw = "for=_hidden;host=\"a;b\"".into();
+        let forwarded: Forwarded = Header::parse_header(&r).unwrap();
+        assert_eq!(forwarded[0].host, Some("a;b".to_owned()));
+
+        let r: Raw = "for=_hidden;host=\"a\\\"b\"".into();
+        let forwarded: Forwarded = Header::parse_header(&r).unwrap();
+        assert_eq!(forwarded[0].host, Some("a\"b".to_owned()));
+        assert_eq!(forwarded.to_string(), "for=_hidden;host=\"a\\\"b\"");
+    }
+
+    #[test]
+    fn test_parse_nodes() {
+        let valid = [
+            "for=_gazonk",
+            "for=\"_hidden.proxy-1\"",
+            "for=\"[2001:db8:cafe::17]\"",
+            "for=\"[2001:db8:cafe::17]:4711\"",
+            "for=\"[v1.fe80::a+en1]\"",
+            "for=\"192.0.2.43:_port\"",
+            "for=\"[::1]:8080\"",
+            "by=unknown",
+            "by=\"unknown:80\"",
+        ];
+        for v in valid.iter() {
+            let r: Raw = (*v).into();
+            assert!(Forwarded::parse_header(&r).is_ok(), "{} should be valid", v);
+        }
+
+        let invalid = [
+            "for=_",
+            "for=\"_bad!id\"",
+            "for=gazonk",
+            "for=\"2001:db8:cafe::17\"",
+            "for=\"[192.0.2.43]\"",
+            "for=\"[2001:db8::17]x\"",
+            "for=\"192.0.2.43:123456\"",
+            "for=\"192.0.2.43:\"",
+            "by=\"unknown:_\"",
+            "for=192.0.2.43:80",
+        ];
+        for v in invalid.iter() {
+            let r: Raw = (*v).into();
+            assert!(Forwarded::parse_header(&r).is_err(), "{} should be invalid", v);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let r: Raw = "for=[2001:db8::1]".into();
+        assert!(Forwarded::parse_header(&r).is_err());
+        let r: Raw = "for=a;for=b".into();
+        assert!(Forwarded::parse_header(&r).is_err());
+        let r: Raw = "for".into();
+        assert!(Forwarded::parse_header(&r).is_err());
+        let r: Raw = "for=gazonk, for=192.0.2.1".into();
+        assert!(Forwarded::parse_header(&r).is_err());
+    }
+
+    #[test]
+    fn test_x_forwarded_round_trip() {
+        let mut headers = Headers::new();
+        headers.set_raw("X-Forwarded-For", "192.0.2.43, 2001:db8:cafe::17");
+        headers.set_raw("X-Forwarded-Host", "example.com");
+        headers.set_raw("X-Forwarded-Proto", "https");
+
+        let forwarded = Forwarded::from_x_forwarded(&headers).unwrap();
+        assert_eq!(forwarded.0, vec![
+            ForwardedElement {
+                for_: Some("192.0.2.43".to_owned()),
+                host: Some("example.com".to_owned()),
+                proto: Some("https".to_owned()),
+                by: None,
+            },
+            ForwardedElement {
+                for_: Some("[2001:db8:cafe::17]".to_owned()),
+                ..ForwardedElement::default()
+            },
+        ]);
+
+        let mut legacy = Headers::new();
+        forwarded.set_x_forwarded(&mut legacy);
+        assert_eq!(legacy.get_raw("X-Forwarded-For").unwrap(),
+                   "192.0.2.43, 2001:db8:cafe::17");
+        assert_eq!(legacy.get_raw("X-Forwarded-Host").unwrap(), "example.com");
+        assert_eq!(legacy.get_raw("X-Forwarded-Proto").unwrap(), "https");
+    }
+
+    #[test]
+    fn test_to_x_forwarded_ports() {
+        let r: Raw = "for=\"192.0.2.43:4711\", by=203.0.113.43, for=\"[::1]:80\"".into();
+        let forwarded: Forwarded = Header::parse_header(&r).unwrap();
+        let mut headers = Headers::new();
+        forwarded.set_x_forwarded(&mut headers);
+        assert_eq!(headers.get_raw("X-Forwarded-For").unwrap(), "192.0.2.43, unknown, ::1");
+        assert!(headers.get_raw("X-Forwarded-Proto").is_none());
+    }
+
+    #[test]
+    fn test_from_x_forwarded_empty() {
+        assert_eq!(Forwarded::from_x_forwarded(&Headers::new()), None);
+    }
+}
+
+bench_header!(bench, Forwarded, { vec![b"for=192.0.2.60;proto=http;by=203.0.113.43".to_vec()] });
+bench_list_header!(bench_list, Forwarded, b"for=192.0.2.60;proto=http;by=203.0.113.43", 16);
+
+standard_header!(Forwarded, FORWARDED);
@@ -28,4 +28,6 @@ header! {
     }
 }
 
+__hyper__from_str!(From);
+
 standard_header!(From, FROM);
@@ -64,6 +64,11 @@ impl Header for Host {
         NAME
     }
 
+    #[inline]
+    fn max_occurrences() -> Option<usize> {
+        Some(1)
+    }
+
     fn parse_header<'a, T>(raw: &'a T) -> ::Result<Host>
     where T: RawLike<'a>
     {
@@ -67,6 +67,8 @@ header! {
     }
 }
 
+__hyper__from_str!(IfMatch);
+
 bench_header!(star, IfMatch, { vec![b"*".to_vec()] });
 bench_header!(single , IfMatch, { vec![b"\"xyzzy\"".to_vec()] });
 bench_header!(multi, IfMatch,
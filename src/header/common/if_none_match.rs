@@ -61,6 +61,8 @@ header! {
     }
 }
 
+__hyper__from_str!(IfNoneMatch);
+
 #[cfg(test)]
 mod tests {
     use super::IfNoneMatch;
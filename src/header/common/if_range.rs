@@ -76,6 +76,8 @@ impl Header for IfRange {
     }
 }
 
+__hyper__from_str!(IfRange);
+
 impl Display for IfRange {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
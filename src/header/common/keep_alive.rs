@@ -0,0 +1,136 @@
+use std::fmt;
+use std::time::Duration;
+
+use header::{Header, RawLike};
+use header::parsing::{duration_to_secs_saturating, from_comma_delimited};
+
+/// `Keep-Alive` header, defined in
+/// [RFC2068](https://tools.ietf.org/html/rfc2068#section-19.7.1.1)
+///
+/// The `Keep-Alive` header field is sent with `Connection: keep-alive` to
+/// advertise how long an idle persistent connection is kept open, and how
+/// many more requests it may carry.
+///
+/// Parameters other than `timeout` and `max` are ignored.
+///
+/// # ABNF
+///
+/// ```text
+/// Keep-Alive = 1#keep-alive-param
+/// keep-alive-param = token [ "=" ( token / quoted-string ) ]
+/// ```
+///
+/// # Example values
+///
+/// * `timeout=5, max=100`
+/// * `timeout=5`
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use hyperx::header::{Headers, KeepAlive};
+///
+/// let mut headers = Headers::new();
+/// headers.set(KeepAlive { timeout: Some(5), max: Some(100) });
+/// headers.set(KeepAlive::from(Duration::from_secs(5)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeepAlive {
+    /// The number of seconds an idle connection is kept open.
+    pub timeout: Option<u32>,
+    /// The maximum number of further requests on the connection.
+    pub max: Option<u32>,
+}
+
+impl From<Duration> for KeepAlive {
+    /// A `Keep-Alive` with the given timeout, converted with
+    /// `duration_to_secs_saturating`, and no `max`.
+    fn from(timeout: Duration) -> KeepAlive {
+        KeepAlive {
+            timeout: Some(duration_to_secs_saturating(timeout)),
+            max: None,
+        }
+    }
+}
+
+impl Header for KeepAlive {
+    fn header_name() -> &'static str {
+        static NAME: &'static str = "Keep-Alive";
+        NAME
+    }
+
+    fn parse_header<'a, T>(raw: &'a T) -> ::Result<KeepAlive>
+    where T: RawLike<'a>
+    {
+        let params: Vec<String> = try!(from_comma_delimited(raw));
+        if params.is_empty() {
+            return Err(::Error::Header);
+        }
+        let mut keep_alive = KeepAlive { timeout: None, max: None };
+        for param in &params {
+            let mut parts = param.splitn(2, '=');
+            let name = parts.next().unwrap_or("").trim();
+            let value = parts.next().map(|v| v.trim().trim_matches('"'));
+            let field = if name.eq_ignore_ascii_case("timeout") {
+                &mut keep_alive.timeout
+            } else if name.eq_ignore_ascii_case("max") {
+                &mut keep_alive.max
+            } else {
+                continue;
+            };
+            match value.map(str::parse) {
+                Some(Ok(n)) if field.is_none() => *field = Some(n),
+                _ => return Err(::Error::Header),
+            }
+        }
+        Ok(keep_alive)
+    }
+
+    fn fmt_header(&self, f: &mut ::header::Formatter) -> fmt::Result {
+        f.fmt_line(self)
+    }
+}
+
+__hyper__from_str!(KeepAlive);
+
+impl fmt::Display for KeepAlive {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.timeout, self.max) {
+            (Some(timeout), Some(max)) => write!(f, "timeout={}, max={}", timeout, max),
+            (Some(timeout), None) => write!(f, "timeout={}", timeout),
+            (None, Some(max)) => write!(f, "max={}", max),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeepAlive;
+    use header::{Header, Raw};
+    use test_support::assert_roundtrip;
+
+    #[test]
+    fn test_parse() {
+        let r: Raw = "timeout=5, max=100".into();
+        assert_eq!(KeepAlive::parse_header(&r).unwrap(),
+                   KeepAlive { timeout: Some(5), max: Some(100) });
+        let r: Raw = "Timeout=\"5\", x-other=1".into();
+        assert_eq!(KeepAlive::parse_header(&r).unwrap(),
+                   KeepAlive { timeout: Some(5), max: None });
+
+        for bad in &["", "timeout", "timeout=x", "timeout=1, timeout=2", "max=-1"] {
+            let r: Raw = (*bad).into();
+            assert!(KeepAlive::parse_header(&r).is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        assert_roundtrip::<KeepAlive>(b"timeout=5, max=100");
+        assert_roundtrip::<KeepAlive>(b"max=3");
+    }
+}
+
+bench_header!(bench, KeepAlive, { vec![b"timeout=5, max=100".to_vec()] });
@@ -48,6 +48,8 @@ impl Header for LastEventId {
     }
 }
 
+__hyper__from_str!(LastEventId);
+
 impl Display for LastEventId {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -109,6 +109,37 @@ pub struct LinkValue {
     media_type: Option<Mime>,
 }
 
+/// The pagination targets of a `Link` header, as commonly used by web APIs
+/// to link a page of results to its neighbors.
+///
+/// Each target is the URI-Reference of the first `link-value` with the
+/// corresponding relation type. Targets are returned as given, and may be
+/// relative to the request URI.
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{Header, Link, Raw};
+///
+/// let r: Raw = "<https://api.example.com/items?page=2>; rel=\"next\", \
+///               <https://api.example.com/items?page=9>; rel=\"last\"".into();
+/// let link = Link::parse_header(&r).unwrap();
+/// let pages = link.pagination();
+/// assert_eq!(pages.next, Some("https://api.example.com/items?page=2"));
+/// assert_eq!(pages.prev, None);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Pagination<'a> {
+    /// Target of the `next` relation.
+    pub next: Option<&'a str>,
+    /// Target of the `prev` (or synonymous `previous`) relation.
+    pub prev: Option<&'a str>,
+    /// Target of the `first` relation.
+    pub first: Option<&'a str>,
+    /// Target of the `last` relation.
+    pub last: Option<&'a str>,
+}
+
 /// A Media Descriptors Enum based on:
 /// [https://www.w3.org/TR/html401/types.html#h-6.13][url]
 ///
@@ -244,6 +275,27 @@ impl Link {
     pub fn push_value(&mut self, link_value: LinkValue) {
         self.values.push(link_value);
     }
+
+    /// Get the `next`, `prev`, `first` and `last` pagination targets.
+    pub fn pagination(&self) -> Pagination {
+        let mut pages = Pagination::default();
+        for value in &self.values {
+            for rel in value.rel().unwrap_or(&[]) {
+                let target = match *rel {
+                    RelationType::Next => &mut pages.next,
+                    RelationType::Prev |
+                    RelationType::Previous => &mut pages.prev,
+                    RelationType::First => &mut pages.first,
+                    RelationType::Last => &mut pages.last,
+                    _ => continue,
+                };
+                if target.is_none() {
+                    *target = Some(value.link());
+                }
+            }
+        }
+        pages
+    }
 }
 
 impl LinkValue {
@@ -896,7 +948,8 @@ mod tests {
     use std::fmt;
     use std::fmt::Write;
 
-    use super::{Link, LinkValue, MediaDesc, RelationType, SplitAsciiUnquoted};
+    use super::{Link, LinkValue, MediaDesc, Pagination, RelationType};
+    use super::SplitAsciiUnquoted;
     use super::{fmt_delimited, verify_and_trim};
 
     use header::{Header, Raw};
@@ -993,6 +1046,32 @@ mod tests {
         assert_eq!(link_header, expected_link_header);
     }
 
+    #[test]
+    fn test_link_pagination() {
+        let link_header = b"<https://api.example.com/items?page=3>; rel=\"next\", \
+            <https://api.example.com/items?page=1>; rel=\"prev first\", \
+            <https://api.example.com/items?page=9>; rel=\"last\", \
+            <https://api.example.com/items?page=4>; rel=\"next\"";
+
+        let r: Raw = vec![link_header.to_vec()].into();
+        let link: Link = Header::parse_header(&r).unwrap();
+        assert_eq!(link.pagination(), Pagination {
+            next: Some("https://api.example.com/items?page=3"),
+            prev: Some("https://api.example.com/items?page=1"),
+            first: Some("https://api.example.com/items?page=1"),
+            last: Some("https://api.example.com/items?page=9"),
+        });
+
+        let link = Link::new(vec![
+            LinkValue::new("/chapter1").push_rel(RelationType::Previous),
+            LinkValue::new("/index").push_rel(RelationType::Index),
+        ]);
+        assert_eq!(link.pagination(), Pagination {
+            prev: Some("/chapter1"),
+            ..Pagination::default()
+        });
+    }
+
     #[test]
     fn test_link_parsing_errors() {
         let link_a  = b"http://example.com/TheBook/chapter2; \
@@ -1070,5 +1149,6 @@ mod tests {
 }
 
 bench_header!(bench_link, Link, { vec![b"<http://example.com/TheBook/chapter2>; rel=\"previous\"; rev=next; title=\"previous chapter\"; type=\"text/html\"; media=\"screen, tty\"".to_vec()] });
+bench_list_header!(bench_link_list, Link, b"<https://example.com/page/2>; rel=\"next\"", 16);
 
 standard_header!(Link, LINK);
@@ -43,6 +43,8 @@ header! {
 
 }
 
+__hyper__from_str!(Location);
+
 bench_header!(bench, Location, { vec![b"http://foo.com/hello:3000".to_vec()] });
 
 standard_header!(Location, LOCATION);
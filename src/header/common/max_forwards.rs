@@ -0,0 +1,138 @@
+use header::{Headers, ViaEntry};
+
+header! {
+    /// `Max-Forwards` header, defined in
+    /// [RFC7231](https://tools.ietf.org/html/rfc7231#section-5.1.2)
+    ///
+    /// The `Max-Forwards` header field provides a mechanism with the TRACE
+    /// and OPTIONS request methods to limit the number of times that the
+    /// request is forwarded by proxies.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Max-Forwards = 1*DIGIT
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `10`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, MaxForwards};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(MaxForwards(10));
+    /// ```
+    (MaxForwards, "Max-Forwards") => [u32]
+
+    test_max_forwards {
+        test_header!(test1, vec![b"10"]);
+        test_header!(test2, vec![b"0"], Some(HeaderField(0)));
+        test_header!(test3, vec![b"-1"], None::<HeaderField>);
+    }
+}
+
+__hyper__from_str!(MaxForwards);
+
+/// What a proxy should do with a TRACE or OPTIONS request, as decided by
+/// `MaxForwards::forward`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaxForwardsAction {
+    /// Forward the request, with its headers updated.
+    Forward,
+    /// Respond as the final recipient, rather than forward the request.
+    Respond,
+}
+
+impl MaxForwards {
+    /// Apply the `Max-Forwards` rules of a proxy to the headers of a TRACE
+    /// or OPTIONS request.
+    ///
+    /// If `Max-Forwards` is zero, the proxy must respond to the request
+    /// itself, and the headers are left unchanged. This is also the case for
+    /// an invalid `Max-Forwards`, rather than forwarding without a limit.
+    /// Otherwise, any `Max-Forwards` is decremented and the given entry
+    /// appended to `Via`, ready for the request to be forwarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, MaxForwards, MaxForwardsAction, Via, ViaEntry};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(MaxForwards(1));
+    ///
+    /// let via = ViaEntry::new("1.1", "proxy.example.com");
+    /// assert_eq!(MaxForwards::forward(&mut headers, via.clone()), MaxForwardsAction::Forward);
+    /// assert_eq!(headers.get(), Some(&MaxForwards(0)));
+    /// assert_eq!(headers.get(), Some(&Via(vec![via.clone()])));
+    ///
+    /// assert_eq!(MaxForwards::forward(&mut headers, via), MaxForwardsAction::Respond);
+    /// ```
+    pub fn forward(headers: &mut Headers, via: ViaEntry) -> MaxForwardsAction {
+        match headers.get::<MaxForwards>().map(|max| max.0) {
+            Some(0) => return MaxForwardsAction::Respond,
+            Some(n) => headers.set(MaxForwards(n - 1)),
+            None if headers.has::<MaxForwards>() => return MaxForwardsAction::Respond,
+            None => (),
+        }
+        headers.append_raw("Via", via.to_string());
+        MaxForwardsAction::Forward
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MaxForwards, MaxForwardsAction};
+    use header::{Headers, Via, ViaEntry};
+
+    #[test]
+    fn test_forward_decrement() {
+        let mut headers = Headers::new();
+        headers.set(MaxForwards(5));
+        headers.set_raw("Via", "1.0 fred");
+        let action = MaxForwards::forward(&mut headers, ViaEntry::new("1.1", "p.example.net"));
+        assert_eq!(action, MaxForwardsAction::Forward);
+        assert_eq!(headers.get(), Some(&MaxForwards(4)));
+        assert_eq!(headers.get(), Some(&Via(vec![
+            ViaEntry::new("1.0", "fred"),
+            ViaEntry::new("1.1", "p.example.net"),
+        ])));
+    }
+
+    #[test]
+    fn test_forward_without_max_forwards() {
+        let mut headers = Headers::new();
+        let action = MaxForwards::forward(&mut headers, ViaEntry::new("1.1", "p.example.net"));
+        assert_eq!(action, MaxForwardsAction::Forward);
+        assert!(!headers.has::<MaxForwards>());
+        assert_eq!(headers.get_raw("via").unwrap(), "1.1 p.example.net");
+    }
+
+    #[test]
+    fn test_respond_when_invalid() {
+        let mut headers = Headers::new();
+        headers.set_raw("Max-Forwards", "ten");
+        let action = MaxForwards::forward(&mut headers, ViaEntry::new("1.1", "p.example.net"));
+        assert_eq!(action, MaxForwardsAction::Respond);
+        assert_eq!(headers.get_raw("Max-Forwards").unwrap(), "ten");
+        assert!(!headers.has::<Via>());
+    }
+
+    #[test]
+    fn test_respond_at_zero() {
+        let mut headers = Headers::new();
+        headers.set(MaxForwards(0));
+        let action = MaxForwards::forward(&mut headers, ViaEntry::new("1.1", "p.example.net"));
+        assert_eq!(action, MaxForwardsAction::Respond);
+        assert_eq!(headers.get(), Some(&MaxForwards(0)));
+        assert!(!headers.has::<Via>());
+    }
+}
+
+bench_header!(bench, MaxForwards, { vec![b"10".to_vec()] });
+
+standard_header!(MaxForwards, MAX_FORWARDS);
@@ -9,6 +9,8 @@
 pub use self::accept_charset::AcceptCharset;
 pub use self::accept_encoding::AcceptEncoding;
 pub use self::accept_language::AcceptLanguage;
+pub use self::accept_patch::{AcceptPatch, UnsupportedMediaType};
+pub use self::accept_post::AcceptPost;
 pub use self::accept_ranges::{AcceptRanges, RangeUnit};
 pub use self::accept::Accept;
 pub use self::access_control_allow_credentials::AccessControlAllowCredentials;
@@ -19,22 +21,27 @@ pub use self::access_control_expose_headers::AccessControlExposeHeaders;
 pub use self::access_control_max_age::AccessControlMaxAge;
 pub use self::access_control_request_headers::AccessControlRequestHeaders;
 pub use self::access_control_request_method::AccessControlRequestMethod;
+pub use self::age::Age;
 pub use self::allow::Allow;
 pub use self::authorization::{Authorization, Scheme, Basic, Bearer};
-pub use self::cache_control::{CacheControl, CacheDirective};
+pub use self::cache_control::{CacheControl, CacheDirective, DirectiveContext};
+pub use self::clear_site_data::{ClearSiteData, DataType};
 pub use self::connection::{Connection, ConnectionOption};
 pub use self::content_disposition::{ContentDisposition, DispositionType, DispositionParam};
-pub use self::content_encoding::ContentEncoding;
+pub use self::content_encoding::{ContentEncoding, DoubleCompression};
 pub use self::content_language::ContentLanguage;
 pub use self::content_length::ContentLength;
 pub use self::content_location::ContentLocation;
-pub use self::content_range::{ContentRange, ContentRangeSpec};
-pub use self::content_type::ContentType;
+pub use self::content_security_policy::{ContentSecurityPolicy, CspDirective, CspHashAlgo, CspSource};
+pub use self::content_range::{ContentRange, ContentRangeSpec, ByteRangesPart, parse_byteranges};
+pub use self::content_type::{ContentType, BoundaryError, BodyFormat};
 pub use self::cookie::{Cookie, CookieIter};
 pub use self::date::Date;
-pub use self::etag::ETag;
+pub use self::etag::{ETag, variant_etag};
 pub use self::expect::Expect;
 pub use self::expires::Expires;
+pub use self::feature_policy::FeaturePolicy;
+pub use self::forwarded::{Forwarded, ForwardedElement};
 pub use self::from::From;
 pub use self::host::Host;
 pub use self::if_match::IfMatch;
@@ -42,28 +49,42 @@ pub use self::if_modified_since::IfModifiedSince;
 pub use self::if_none_match::IfNoneMatch;
 pub use self::if_range::IfRange;
 pub use self::if_unmodified_since::IfUnmodifiedSince;
+pub use self::keep_alive::KeepAlive;
 pub use self::last_event_id::LastEventId;
 pub use self::last_modified::LastModified;
-pub use self::link::{Link, LinkValue, RelationType, MediaDesc};
+pub use self::link::{Link, LinkValue, RelationType, MediaDesc, Pagination};
 pub use self::location::Location;
+pub use self::max_forwards::{MaxForwards, MaxForwardsAction};
 pub use self::origin::Origin;
+pub use self::permissions_policy::{PermissionsPolicy, PolicyDirective, AllowlistEntry};
 pub use self::pragma::Pragma;
-pub use self::prefer::{Prefer, Preference};
+pub use self::prefer::{Prefer, Preference, Handling};
 pub use self::preference_applied::PreferenceApplied;
+pub use self::priority::Priority;
 pub use self::proxy_authorization::ProxyAuthorization;
 pub use self::range::{Range, ByteRangeSpec};
 pub use self::referer::Referer;
 pub use self::referrer_policy::ReferrerPolicy;
 pub use self::retry_after::RetryAfter;
+pub use self::sec_websocket_extensions::{SecWebSocketExtensions, WebSocketExtension};
+pub use self::sec_websocket_key::{SecWebSocketKey, HandshakeError, validate_websocket_handshake};
+pub use self::sec_websocket_protocol::{SecWebSocketProtocol, select_subprotocol};
+pub use self::sec_websocket_version::SecWebSocketVersion;
+pub use self::security_headers::{SecurityHeaders, FrameOptions};
 pub use self::server::Server;
 pub use self::set_cookie::SetCookie;
 pub use self::strict_transport_security::StrictTransportSecurity;
 pub use self::te::Te;
 pub use self::transfer_encoding::TransferEncoding;
-pub use self::upgrade::{Upgrade, Protocol, ProtocolName};
+pub use self::upgrade::{Upgrade, Protocol, ProtocolName, UpgradeMismatch};
 pub use self::user_agent::UserAgent;
 pub use self::vary::Vary;
+pub use self::via::{Via, ViaEntry};
+pub use self::want_digest::WantDigest;
 pub use self::warning::Warning;
+pub use self::x_forwarded_for::{XForwardedFor, ForwardedNode};
+pub use self::x_forwarded_host::XForwardedHost;
+pub use self::x_forwarded_proto::XForwardedProto;
 
 #[doc(hidden)]
 #[macro_export]
@@ -97,6 +118,36 @@ macro_rules! bench_header(
     }
 );
 
+// Benchmark parsing a comma-delimited list header, with a value of $count
+// copies of $item, reporting throughput in bytes of the value.
+macro_rules! bench_list_header(
+    ($name:ident, $ty:ty, $item:expr, $count:expr) => {
+        #[cfg(test)]
+        #[cfg(feature = "nightly")]
+        mod $name {
+            use test::Bencher;
+            use super::*;
+
+            use header::{Header, Raw};
+
+            fn value() -> Vec<u8> {
+                let items: Vec<&[u8]> = vec![&$item[..]; $count];
+                items.join(&b", "[..])
+            }
+
+            #[bench]
+            fn bench_parse_list(b: &mut Bencher) {
+                let value = value();
+                b.bytes = value.len() as u64;
+                let val: Raw = value.into();
+                b.iter(|| {
+                    let _: $ty = Header::parse_header(&val).unwrap();
+                });
+            }
+        }
+    }
+);
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __hyper__deref {
@@ -119,6 +170,58 @@ macro_rules! __hyper__deref {
     }
 }
 
+// Inherent list methods, applied to each list header separately rather than
+// by `header!`, so that custom headers defined with `header!` are unaffected.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __hyper__list {
+    ($from:ident => $item:ty) => {
+        impl $from {
+            /// Appends an item to the end of the list.
+            #[inline]
+            pub fn push(&mut self, item: $item) {
+                self.0.push(item)
+            }
+
+            /// Removes and returns the item at `index`, shifting later items
+            /// down.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is out of bounds.
+            #[inline]
+            pub fn remove(&mut self, index: usize) -> $item {
+                self.0.remove(index)
+            }
+
+            /// Returns `true` if the list contains an item equal to `item`.
+            #[inline]
+            pub fn contains(&self, item: &$item) -> bool {
+                self.0.contains(item)
+            }
+        }
+    }
+}
+
+// `FromStr` via `Header::parse_header`, applied to each header separately
+// rather than by `header!`, so that custom headers defined with `header!` may
+// still implement `FromStr` themselves.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __hyper__from_str {
+    ($id:ident) => {
+        impl ::std::str::FromStr for $id {
+            type Err = $crate::Error;
+
+            /// Parse a single line header value with `Header::parse_header`.
+            fn from_str(s: &str) -> $crate::Result<$id> {
+                let raw: $crate::header::Raw = s.into();
+                <$id as $crate::header::Header>::parse_header(&raw)
+            }
+        }
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __hyper__tm {
@@ -191,6 +294,30 @@ macro_rules! test_header {
 }
 
 /// Create a custom header type.
+///
+/// The type does not implement `FromStr`, or any inherent methods, so that
+/// these may be added as needed.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate hyperx;
+/// use std::str::FromStr;
+///
+/// header! { (XTags, "X-Tags") => (String)+ }
+///
+/// impl FromStr for XTags {
+///     type Err = hyperx::Error;
+///     fn from_str(s: &str) -> hyperx::Result<XTags> {
+///         Ok(XTags(s.split('/').map(ToOwned::to_owned).collect()))
+///     }
+/// }
+///
+/// fn main() {
+///     let tags: XTags = "a/b".parse().unwrap();
+///     assert_eq!(tags.to_string(), "a, b");
+/// }
+/// ```
 #[macro_export]
 macro_rules! header {
     // $a:meta: Attributes associated with the header item (usually docs)
@@ -200,35 +327,36 @@ macro_rules! header {
 
     // List header, zero or more items
     ($(#[$a:meta])*($id:ident, $n:expr) => ($item:ty)*) => {
-        $(#[$a])*
-        #[derive(Clone, Debug, PartialEq)]
-        pub struct $id(pub Vec<$item>);
-        __hyper__deref!($id => Vec<$item>);
-        impl $crate::header::Header for $id {
-            fn header_name() -> &'static str {
-                static NAME: &'static str = $n;
-                NAME
-            }
-            #[inline]
-            fn parse_header<'a, T>(raw: &'a T) -> $crate::Result<Self>
-            where T: $crate::header::RawLike<'a>
-            {
-                $crate::header::parsing::from_comma_delimited(raw).map($id)
-            }
-            #[inline]
-            fn fmt_header(&self, f: &mut $crate::header::Formatter) -> ::std::fmt::Result {
-                f.fmt_line(self)
-            }
-        }
-        impl ::std::fmt::Display for $id {
-            #[inline]
-            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-                $crate::header::parsing::fmt_comma_delimited(f, &self.0[..])
-            }
+        header! {
+            $(#[$a])*
+            ($id, $n) => list ($item) |_: &$item| true
         }
     };
     // List header, one or more items
     ($(#[$a:meta])*($id:ident, $n:expr) => ($item:ty)+) => {
+        header! {
+            $(#[$a])*
+            ($id, $n) => list ($item) |_: &$item| true
+        }
+    };
+    // List header, zero or more items, each of which must satisfy the
+    // predicate function $valid
+    ($(#[$a:meta])*($id:ident, $n:expr) => ($item:ty)* if $valid:ident) => {
+        header! {
+            $(#[$a])*
+            ($id, $n) => list ($item) $valid
+        }
+    };
+    // List header, one or more items, each of which must satisfy the
+    // predicate function $valid
+    ($(#[$a:meta])*($id:ident, $n:expr) => ($item:ty)+ if $valid:ident) => {
+        header! {
+            $(#[$a])*
+            ($id, $n) => list ($item) $valid
+        }
+    };
+    // List header (internal), failing if any item does not satisfy $valid
+    ($(#[$a:meta])*($id:ident, $n:expr) => list ($item:ty) $valid:expr) => {
         $(#[$a])*
         #[derive(Clone, Debug, PartialEq)]
         pub struct $id(pub Vec<$item>);
@@ -243,7 +371,8 @@ macro_rules! header {
             fn parse_header<'a, T>(raw: &'a T) -> $crate::Result<Self>
             where T: $crate::header::RawLike<'a>
             {
-                $crate::header::parsing::from_comma_delimited(raw).map($id)
+                $crate::header::parsing::from_comma_delimited_valid(raw, $valid)
+                    .map($id)
             }
             #[inline]
             fn fmt_header(&self, f: &mut $crate::header::Formatter) -> ::std::fmt::Result {
@@ -270,6 +399,10 @@ macro_rules! header {
                 NAME
             }
             #[inline]
+            fn max_occurrences() -> Option<usize> {
+                Some(1)
+            }
+            #[inline]
             fn parse_header<'a, T>(raw: &'a T) -> $crate::Result<Self>
             where T: $crate::header::RawLike<'a>
             {
@@ -300,6 +433,10 @@ macro_rules! header {
                 NAME
             }
             #[inline]
+            fn max_occurrences() -> Option<usize> {
+                Some(1)
+            }
+            #[inline]
             fn parse_header<'a, T>(raw: &'a T) -> $crate::Result<Self>
             where T: $crate::header::RawLike<'a>
             {
@@ -342,6 +479,10 @@ macro_rules! header {
                 NAME
             }
             #[inline]
+            fn max_occurrences() -> Option<usize> {
+                Some(1)
+            }
+            #[inline]
             fn parse_header<'a, T>(raw: &'a T) -> $crate::Result<Self>
             where T: $crate::header::RawLike<'a>
             {
@@ -405,6 +546,22 @@ macro_rules! header {
     };
 
     // optional test module
+    ($(#[$a:meta])*($id:ident, $n:expr) => ($item:ty)* if $valid:ident $tm:ident{$($tf:item)*}) => {
+        header! {
+            $(#[$a])*
+            ($id, $n) => ($item)* if $valid
+        }
+
+        __hyper__tm! { $id, $tm { $($tf)* }}
+    };
+    ($(#[$a:meta])*($id:ident, $n:expr) => ($item:ty)+ if $valid:ident $tm:ident{$($tf:item)*}) => {
+        header! {
+            $(#[$a])*
+            ($id, $n) => ($item)+ if $valid
+        }
+
+        __hyper__tm! { $id, $tm { $($tf)* }}
+    };
     ($(#[$a:meta])*($id:ident, $n:expr) => ($item:ty)* $tm:ident{$($tf:item)*}) => {
         header! {
             $(#[$a])*
@@ -466,12 +623,22 @@ macro_rules! standard_header {
                 ::http::header::$hname
             }
         }
+
+        // Every standard header must also be in the `Headers` registry.
+        #[cfg(test)]
+        #[test]
+        fn test_standard_header_registered() {
+            let name = <$local as $crate::header::Header>::header_name();
+            assert!(::header::registered(name).is_some(), "{} is not registered", name);
+        }
     }
 }
 
 mod accept_charset;
 mod accept_encoding;
 mod accept_language;
+mod accept_patch;
+mod accept_post;
 mod accept_ranges;
 mod accept;
 mod access_control_allow_credentials;
@@ -482,9 +649,11 @@ mod access_control_expose_headers;
 mod access_control_max_age;
 mod access_control_request_headers;
 mod access_control_request_method;
+mod age;
 mod allow;
 mod authorization;
 mod cache_control;
+mod clear_site_data;
 mod connection;
 mod content_disposition;
 mod content_encoding;
@@ -492,12 +661,15 @@ mod content_language;
 mod content_length;
 mod content_location;
 mod content_range;
+mod content_security_policy;
 mod content_type;
 mod cookie;
 mod date;
 mod etag;
 mod expect;
 mod expires;
+mod feature_policy;
+mod forwarded;
 mod from;
 mod host;
 mod if_match;
@@ -505,19 +677,28 @@ mod if_modified_since;
 mod if_none_match;
 mod if_range;
 mod if_unmodified_since;
+mod keep_alive;
 mod last_event_id;
 mod last_modified;
 mod link;
 mod location;
+mod max_forwards;
 mod origin;
+mod permissions_policy;
 mod pragma;
 mod prefer;
 mod preference_applied;
+mod priority;
 mod proxy_authorization;
 mod range;
 mod referer;
 mod referrer_policy;
 mod retry_after;
+mod sec_websocket_extensions;
+mod sec_websocket_key;
+mod sec_websocket_protocol;
+mod sec_websocket_version;
+mod security_headers;
 mod server;
 mod set_cookie;
 mod strict_transport_security;
@@ -526,4 +707,9 @@ mod transfer_encoding;
 mod upgrade;
 mod user_agent;
 mod vary;
+mod via;
+mod want_digest;
 mod warning;
+mod x_forwarded_for;
+mod x_forwarded_host;
+mod x_forwarded_proto;
@@ -0,0 +1,287 @@
+use std::fmt;
+
+use header::{FeaturePolicy, Header, RawLike, parsing};
+
+/// `Permissions-Policy` header, defined in
+/// [Permissions Policy](https://www.w3.org/TR/permissions-policy-1/#permissions-policy-http-header-field)
+///
+/// The `Permissions-Policy` response header field controls which origins
+/// may use browser features, such as `geolocation` or `camera`, in the
+/// document and its frames. It is a structured field dictionary, mapping
+/// each feature to an allowlist.
+///
+/// This replaces the legacy `Feature-Policy` header, and
+/// `from_feature_policy` and `to_feature_policy` convert between the two
+/// syntaxes, for sites which need to send both while migrating. The
+/// `FeaturePolicy` header type shares the same directives, and converts
+/// with `From`. Parameters
+/// of dictionary members are ignored.
+///
+/// # Example values
+///
+/// * `geolocation=(self "https://example.com"), camera=()`
+/// * `fullscreen=*`
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{Headers, PermissionsPolicy, PolicyDirective, AllowlistEntry};
+///
+/// let mut headers = Headers::new();
+/// headers.set(PermissionsPolicy(vec![
+///     PolicyDirective::new("geolocation", vec![AllowlistEntry::Self_]),
+///     PolicyDirective::new("camera", vec![]),
+/// ]));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct PermissionsPolicy(pub Vec<PolicyDirective>);
+
+__hyper__deref!(PermissionsPolicy => Vec<PolicyDirective>);
+
+/// A policy directive, mapping a feature to its allowlist.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PolicyDirective {
+    /// The feature name, such as `geolocation`.
+    pub feature: String,
+    /// The origins allowed to use the feature, empty if none are.
+    pub allowlist: Vec<AllowlistEntry>,
+}
+
+impl PolicyDirective {
+    /// Create a new directive from a feature name and its allowlist.
+    pub fn new<F: Into<String>>(feature: F, allowlist: Vec<AllowlistEntry>) -> PolicyDirective {
+        PolicyDirective {
+            feature: feature.into(),
+            allowlist: allowlist,
+        }
+    }
+}
+
+/// An entry of a `PolicyDirective` allowlist.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum AllowlistEntry {
+    /// `*`, all origins
+    Any,
+    /// `self`, the origin of the document
+    Self_,
+    /// `src`, the origin of an iframe's `src`
+    Src,
+    /// A serialized origin, such as `https://example.com`
+    Origin(String),
+}
+
+impl PermissionsPolicy {
+    /// Get a directive by feature name.
+    pub fn directive(&self, feature: &str) -> Option<&PolicyDirective> {
+        self.iter().find(|d| d.feature == feature)
+    }
+
+    /// Convert a legacy `Feature-Policy` header value.
+    ///
+    /// Features are separated by `;`, each followed by its space-separated
+    /// allowlist, where `'none'` stands for an empty allowlist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::PermissionsPolicy;
+    ///
+    /// let policy = PermissionsPolicy::from_feature_policy(
+    ///     "geolocation 'self' https://example.com; camera 'none'").unwrap();
+    /// assert_eq!(policy.to_string(),
+    ///            "geolocation=(self \"https://example.com\"), camera=()");
+    /// ```
+    pub fn from_feature_policy(value: &str) -> ::Result<PermissionsPolicy> {
+        value.parse::<FeaturePolicy>().map(PermissionsPolicy::from)
+    }
+
+    /// Format as a legacy `Feature-Policy` header value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::PermissionsPolicy;
+    ///
+    /// let policy: PermissionsPolicy = "fullscreen=*, camera=()".parse().unwrap();
+    /// assert_eq!(policy.to_feature_policy(), "fullscreen *; camera 'none'");
+    /// ```
+    pub fn to_feature_policy(&self) -> String {
+        FeaturePolicy(self.0.clone()).to_string()
+    }
+}
+
+impl From<FeaturePolicy> for PermissionsPolicy {
+    fn from(policy: FeaturePolicy) -> PermissionsPolicy {
+        PermissionsPolicy(policy.0)
+    }
+}
+
+impl Header for PermissionsPolicy {
+    fn header_name() -> &'static str {
+        static NAME: &'static str = "Permissions-Policy";
+        NAME
+    }
+
+    fn parse_header<'a, T>(raw: &'a T) -> ::Result<PermissionsPolicy>
+    where T: RawLike<'a>
+    {
+        let mut directives = Vec::new();
+        for member in parsing::comma_delimited_iter(raw) {
+            directives.push(try!(parse_member(try!(member))));
+        }
+        if directives.is_empty() {
+            Err(::Error::Header)
+        } else {
+            Ok(PermissionsPolicy(directives))
+        }
+    }
+
+    fn fmt_header(&self, f: &mut ::header::Formatter) -> fmt::Result {
+        f.fmt_line(self)
+    }
+}
+
+__hyper__from_str!(PermissionsPolicy);
+
+// member = key "=" ( inner-list / item ) parameters
+fn parse_member(member: &str) -> ::Result<PolicyDirective> {
+    let eq = try!(member.find('=').ok_or(::Error::Header));
+    let (key, value) = (&member[..eq], member[eq + 1..].trim());
+    if !is_key(key) {
+        return Err(::Error::Header);
+    }
+    let allowlist = if value.starts_with('(') {
+        let end = try!(value.find(')').ok_or(::Error::Header));
+        let mut allowlist = Vec::new();
+        for item in value[1..end].split_whitespace() {
+            allowlist.push(try!(parse_item(item)));
+        }
+        allowlist
+    } else {
+        let item = value.split(';').next().unwrap_or("").trim();
+        vec![try!(parse_item(item))]
+    };
+    Ok(PolicyDirective::new(key, allowlist))
+}
+
+// key = ( lcalpha / "*" ) *( lcalpha / DIGIT / "_" / "-" / "." / "*" )
+fn is_key(key: &str) -> bool {
+    let mut bytes = key.bytes();
+    match bytes.next() {
+        Some(b) if b.is_ascii_lowercase() || b == b'*' => {
+            bytes.all(|b| {
+                b.is_ascii_lowercase() || b.is_ascii_digit() ||
+                    b == b'_' || b == b'-' || b == b'.' || b == b'*'
+            })
+        }
+        _ => false,
+    }
+}
+
+fn parse_item(item: &str) -> ::Result<AllowlistEntry> {
+    match item {
+        "*" => Ok(AllowlistEntry::Any),
+        "self" => Ok(AllowlistEntry::Self_),
+        "src" => Ok(AllowlistEntry::Src),
+        _ if item.len() > 2 && item.starts_with('"') && item.ends_with('"') => {
+            Ok(AllowlistEntry::Origin(item[1..item.len() - 1].to_owned()))
+        }
+        _ => Err(::Error::Header),
+    }
+}
+
+impl fmt::Display for PermissionsPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, directive) in self.iter().enumerate() {
+            if i > 0 {
+                try!(f.write_str(", "));
+            }
+            try!(fmt::Display::fmt(directive, f));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PolicyDirective {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "{}=", self.feature));
+        if self.allowlist.contains(&AllowlistEntry::Any) {
+            return f.write_str("*");
+        }
+        try!(f.write_str("("));
+        for (i, entry) in self.allowlist.iter().enumerate() {
+            if i > 0 {
+                try!(f.write_str(" "));
+            }
+            try!(fmt::Display::fmt(entry, f));
+        }
+        f.write_str(")")
+    }
+}
+
+impl fmt::Display for AllowlistEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AllowlistEntry::Any => f.write_str("*"),
+            AllowlistEntry::Self_ => f.write_str("self"),
+            AllowlistEntry::Src => f.write_str("src"),
+            AllowlistEntry::Origin(ref origin) => write!(f, "\"{}\"", origin),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AllowlistEntry, PermissionsPolicy, PolicyDirective};
+    use header::{Header, Raw};
+
+    #[test]
+    fn test_parse() {
+        let r: Raw = "geolocation=(self \"https://x.com\"), camera=(), fullscreen=*;report-to=r"
+            .into();
+        let policy: PermissionsPolicy = Header::parse_header(&r).unwrap();
+        assert_eq!(policy, PermissionsPolicy(vec![
+            PolicyDirective::new("geolocation", vec![
+                AllowlistEntry::Self_,
+                AllowlistEntry::Origin("https://x.com".to_owned()),
+            ]),
+            PolicyDirective::new("camera", vec![]),
+            PolicyDirective::new("fullscreen", vec![AllowlistEntry::Any]),
+        ]));
+        assert_eq!(policy.to_string(),
+                   "geolocation=(self \"https://x.com\"), camera=(), fullscreen=*");
+        assert_eq!(policy.directive("camera").unwrap().allowlist, vec![]);
+
+        let r: Raw = "Geolocation=()".into();
+        assert!(PermissionsPolicy::parse_header(&r).is_err());
+        let r: Raw = "geolocation=(https://x.com)".into();
+        assert!(PermissionsPolicy::parse_header(&r).is_err());
+        let r: Raw = "".into();
+        assert!(PermissionsPolicy::parse_header(&r).is_err());
+    }
+
+    #[test]
+    fn test_from_feature_policy() {
+        let policy = PermissionsPolicy::from_feature_policy(
+            "geolocation 'self' https://x.com; camera 'none'").unwrap();
+        let expected: PermissionsPolicy =
+            "geolocation=(self \"https://x.com\"), camera=()".parse().unwrap();
+        assert_eq!(policy, expected);
+
+        let policy = PermissionsPolicy::from_feature_policy("fullscreen *; ;").unwrap();
+        assert_eq!(policy.to_string(), "fullscreen=*");
+
+        assert!(PermissionsPolicy::from_feature_policy(" ; ").is_err());
+    }
+
+    #[test]
+    fn test_to_feature_policy() {
+        let policy: PermissionsPolicy =
+            "geolocation=(self \"https://x.com\"), camera=(), payment=(src)".parse().unwrap();
+        let value = policy.to_feature_policy();
+        assert_eq!(value, "geolocation 'self' https://x.com; camera 'none'; payment 'src'");
+        assert_eq!(PermissionsPolicy::from_feature_policy(&value).unwrap(), policy);
+    }
+}
+
+bench_header!(bench, PermissionsPolicy, { vec![b"geolocation=(self \"https://x.com\"), camera=()".to_vec()] });
@@ -34,7 +34,7 @@ use header::{Header, RawLike, parsing};
 /// let mut headers = Headers::new();
 /// headers.set(Pragma::Ext("foobar".to_owned()));
 /// ```
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
 pub enum Pragma {
     /// Corresponds to the `no-cache` value.
     NoCache,
@@ -65,6 +65,8 @@ impl Header for Pragma {
     }
 }
 
+__hyper__from_str!(Pragma);
+
 impl fmt::Display for Pragma {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(match *self {
@@ -1,6 +1,6 @@
 use std::fmt;
 use std::str::FromStr;
-use header::{Header, RawLike};
+use header::{Header, PreferenceApplied, RawLike};
 use header::parsing::{from_comma_delimited, fmt_comma_delimited};
 
 /// `Prefer` header, defined in [RFC7240](http://tools.ietf.org/html/rfc7240)
@@ -53,6 +53,56 @@ pub struct Prefer(pub Vec<Preference>);
 
 __hyper__deref!(Prefer => Vec<Preference>);
 
+impl Prefer {
+    /// Get the `handling` preference, if the client sent one.
+    ///
+    /// If it was sent more than once, the first one is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{Handling, Prefer, Preference};
+    ///
+    /// let prefer = Prefer(vec![Preference::RespondAsync, Preference::HandlingStrict]);
+    /// assert_eq!(prefer.handling(), Some(Handling::Strict));
+    /// ```
+    pub fn handling(&self) -> Option<Handling> {
+        self.iter().filter_map(|pref| match *pref {
+            Preference::HandlingStrict => Some(Handling::Strict),
+            Preference::HandlingLenient => Some(Handling::Lenient),
+            _ => None,
+        }).next()
+    }
+}
+
+/// The `handling` preference, which asks a server to either reject
+/// (`strict`) or attempt to process (`lenient`) a request that contains
+/// errors.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Handling {
+    /// "handling=strict"
+    Strict,
+    /// "handling=lenient"
+    Lenient,
+}
+
+impl Handling {
+    /// The `Preference-Applied` header a server should send when it honors
+    /// this preference.
+    pub fn preference_applied(&self) -> PreferenceApplied {
+        PreferenceApplied(vec![Preference::from(*self)])
+    }
+}
+
+impl From<Handling> for Preference {
+    fn from(handling: Handling) -> Preference {
+        match handling {
+            Handling::Strict => Preference::HandlingStrict,
+            Handling::Lenient => Preference::HandlingLenient,
+        }
+    }
+}
+
 impl Header for Prefer {
     fn header_name() -> &'static str {
         static NAME: &'static str = "Prefer";
@@ -75,6 +125,8 @@ impl Header for Prefer {
     }
 }
 
+__hyper__from_str!(Prefer);
+
 impl fmt::Display for Prefer {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt_comma_delimited(f, &self[..])
@@ -82,7 +134,7 @@ impl fmt::Display for Prefer {
 }
 
 /// Prefer contains a list of these preferences.
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub enum Preference {
     /// "respond-async"
     RespondAsync,
@@ -193,6 +245,25 @@ mod tests {
                                            Preference::HandlingStrict])))
     }
 
+    #[test]
+    fn test_handling() {
+        let r: Raw = "respond-async, handling=strict".into();
+        let prefer: Prefer = Header::parse_header(&r).unwrap();
+        assert_eq!(prefer.handling(), Some(Handling::Strict));
+        assert_eq!(prefer.handling().unwrap().preference_applied().to_string(),
+                   "handling=strict");
+
+        let r: Raw = "handling=\"lenient\", handling=strict".into();
+        let prefer: Prefer = Header::parse_header(&r).unwrap();
+        assert_eq!(prefer.handling(), Some(Handling::Lenient));
+        assert_eq!(prefer.handling().unwrap().preference_applied(),
+                   PreferenceApplied(vec![Preference::HandlingLenient]));
+
+        let r: Raw = "return=minimal".into();
+        let prefer: Prefer = Header::parse_header(&r).unwrap();
+        assert_eq!(prefer.handling(), None);
+    }
+
     #[test]
     fn test_parse_extension() {
         let r: Raw = "foo, bar=baz, baz; foo; bar=baz, bux=\"\"; \
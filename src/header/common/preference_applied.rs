@@ -74,6 +74,8 @@ impl Header for PreferenceApplied {
     }
 }
 
+__hyper__from_str!(PreferenceApplied);
+
 impl fmt::Display for PreferenceApplied {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         //TODO: format this without allocating a Vec and cloning contents
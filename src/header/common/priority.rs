@@ -0,0 +1,209 @@
+use std::fmt;
+
+use header::{Header, RawLike};
+use header::parsing::from_comma_delimited;
+
+/// `Priority` header, defined in
+/// [RFC9218](https://tools.ietf.org/html/rfc9218#section-5)
+///
+/// The `Priority` header field carries the priority signal of a request or
+/// response, as a Structured Field dictionary of `u` (urgency) and `i`
+/// (incremental) parameters. A parameter not given takes its default, which
+/// is urgency `3` and non-incremental.
+///
+/// Unknown parameters, and values of the wrong type or out of range, are
+/// ignored.
+///
+/// # ABNF
+///
+/// ```text
+/// Priority = sf-dictionary
+/// ```
+///
+/// # Example values
+///
+/// * `u=5, i`
+/// * `u=0`
+/// * `i=?0`
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{Headers, Priority};
+///
+/// let mut headers = Headers::new();
+/// headers.set(Priority { urgency: Some(5), incremental: Some(true) });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Priority {
+    /// The urgency, from `0` (most urgent) to `7`, if given.
+    pub urgency: Option<u8>,
+    /// Whether the response may be processed incrementally, if given.
+    pub incremental: Option<bool>,
+}
+
+impl Default for Priority {
+    /// A `Priority` with no parameters given, so of the default urgency `3`
+    /// and non-incremental.
+    fn default() -> Priority {
+        Priority { urgency: None, incremental: None }
+    }
+}
+
+impl Priority {
+    /// The default urgency, `3`.
+    pub const DEFAULT_URGENCY: u8 = 3;
+
+    /// The urgency, or the default of `3` if not given.
+    pub fn urgency(&self) -> u8 {
+        self.urgency.unwrap_or(Priority::DEFAULT_URGENCY)
+    }
+
+    /// Whether the response may be processed incrementally, by default
+    /// `false`.
+    pub fn is_incremental(&self) -> bool {
+        self.incremental.unwrap_or(false)
+    }
+
+    /// Apply a reprioritization, such as an HTTP/2 or HTTP/3 PRIORITY_UPDATE
+    /// frame, to this initial priority.
+    ///
+    /// Each parameter given in `update` replaces the current value, while
+    /// those not given in `update` keep their current value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::Priority;
+    ///
+    /// let initial = Priority { urgency: Some(5), incremental: Some(true) };
+    /// let update = Priority { urgency: Some(1), incremental: None };
+    /// assert_eq!(initial.reprioritize(&update),
+    ///            Priority { urgency: Some(1), incremental: Some(true) });
+    /// ```
+    pub fn reprioritize(&self, update: &Priority) -> Priority {
+        Priority {
+            urgency: update.urgency.or(self.urgency),
+            incremental: update.incremental.or(self.incremental),
+        }
+    }
+}
+
+impl Header for Priority {
+    fn header_name() -> &'static str {
+        static NAME: &'static str = "Priority";
+        NAME
+    }
+
+    fn parse_header<'a, T>(raw: &'a T) -> ::Result<Priority>
+    where T: RawLike<'a>
+    {
+        let members: Vec<String> = try!(from_comma_delimited(raw));
+        let mut priority = Priority::default();
+        for member in &members {
+            // member parameters are not used
+            let member = member.split(';').next().unwrap_or("");
+            let mut parts = member.splitn(2, '=');
+            let key = parts.next().unwrap_or("").trim();
+            let value = parts.next().map(str::trim);
+            match (key, value) {
+                ("u", Some(value)) => {
+                    priority.urgency = value.parse().ok().filter(|&u: &u8| u <= 7);
+                },
+                ("i", None) | ("i", Some("?1")) => priority.incremental = Some(true),
+                ("i", Some("?0")) => priority.incremental = Some(false),
+                ("i", Some(_)) => priority.incremental = None,
+                _ => (),
+            }
+        }
+        Ok(priority)
+    }
+
+    fn fmt_header(&self, f: &mut ::header::Formatter) -> fmt::Result {
+        f.fmt_line(self)
+    }
+}
+
+__hyper__from_str!(Priority);
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(urgency) = self.urgency {
+            try!(write!(f, "u={}", urgency));
+            if self.incremental.is_some() {
+                try!(f.write_str(", "));
+            }
+        }
+        match self.incremental {
+            Some(true) => f.write_str("i"),
+            Some(false) => f.write_str("i=?0"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Priority;
+    use header::{Header, Raw};
+    use test_support::assert_roundtrip;
+
+    fn parse(s: &str) -> Priority {
+        let r: Raw = s.into();
+        Priority::parse_header(&r).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("u=5, i"), Priority { urgency: Some(5), incremental: Some(true) });
+        assert_eq!(parse("i=?0"), Priority { urgency: None, incremental: Some(false) });
+        assert_eq!(parse("u=1;x=y, i=?1, x-other=\"a, b\""),
+                   Priority { urgency: Some(1), incremental: Some(true) });
+        // out of range or wrong type values are ignored, the last value wins
+        assert_eq!(parse("u=8, i=1"), Priority { urgency: None, incremental: None });
+        assert_eq!(parse("u=2, u=6"), Priority { urgency: Some(6), incremental: None });
+
+        let p = parse("");
+        assert_eq!(p.urgency(), 3);
+        assert!(!p.is_incremental());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        assert_roundtrip::<Priority>(b"u=5, i");
+        assert_roundtrip::<Priority>(b"u=0");
+        assert_roundtrip::<Priority>(b"i=?0");
+    }
+
+    #[test]
+    fn test_default() {
+        let p = Priority::default();
+        assert_eq!(p, Priority { urgency: None, incremental: None });
+        assert_eq!(p.urgency(), Priority::DEFAULT_URGENCY);
+        assert_eq!(p.urgency(), 3);
+        assert!(!p.is_incremental());
+        assert_eq!(p.to_string(), "");
+
+        let p = Priority { urgency: Some(1), ..Default::default() };
+        assert_eq!(p.to_string(), "u=1");
+    }
+
+    #[test]
+    fn test_reprioritize() {
+        let initial = Priority { urgency: Some(5), incremental: Some(true) };
+
+        let urgency_only = parse("u=1");
+        assert_eq!(initial.reprioritize(&urgency_only),
+                   Priority { urgency: Some(1), incremental: Some(true) });
+
+        let incremental_only = parse("i=?0");
+        assert_eq!(initial.reprioritize(&incremental_only),
+                   Priority { urgency: Some(5), incremental: Some(false) });
+
+        let unset = Priority::default();
+        assert_eq!(initial.reprioritize(&unset), initial);
+        assert!(!unset.reprioritize(&urgency_only).is_incremental());
+    }
+}
+
+bench_header!(bench, Priority, { vec![b"u=5, i".to_vec()] });
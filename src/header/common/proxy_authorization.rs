@@ -110,6 +110,16 @@ impl<S: Scheme + Any> Header for ProxyAuthorization<S> where <S as FromStr>::Err
     }
 }
 
+impl<S: Scheme + Any> FromStr for ProxyAuthorization<S> where <S as FromStr>::Err: 'static {
+    type Err = ::Error;
+
+    /// Parse a single line header value with `Header::parse_header`.
+    fn from_str(s: &str) -> ::Result<ProxyAuthorization<S>> {
+        let raw: ::header::Raw = s.into();
+        ProxyAuthorization::parse_header(&raw)
+    }
+}
+
 impl<S: Scheme> fmt::Display for ProxyAuthorization<S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let Some(scheme) = <S as Scheme>::scheme() {
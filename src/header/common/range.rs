@@ -1,7 +1,7 @@
 use std::fmt::{self, Display};
 use std::str::FromStr;
 
-use header::{Header, RawLike};
+use header::{AcceptRanges, ContentRange, ContentRangeSpec, ContentType, Header, RangeUnit, RawLike};
 use header::parsing::{from_one_raw_str};
 
 /// `Range` header, defined in [RFC7233](https://tools.ietf.org/html/rfc7233#section-3.1)
@@ -162,6 +162,100 @@ impl Range {
     pub fn bytes_multi(ranges: Vec<(u64, u64)>) -> Range {
         Range::Bytes(ranges.iter().map(|r| ByteRangeSpec::FromTo(r.0, r.1)).collect())
     }
+
+    /// Get the unit of this range.
+    pub fn unit(&self) -> RangeUnit {
+        match *self {
+            Range::Bytes(_) => RangeUnit::Bytes,
+            Range::Unregistered(ref unit, _) => RangeUnit::Unregistered(unit.clone()),
+        }
+    }
+
+    /// Returns true if the unit of this range is one advertised by an
+    /// `Accept-Ranges` header, so that the range may be honored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{AcceptRanges, Range, RangeUnit};
+    ///
+    /// let accept = AcceptRanges(vec![RangeUnit::Unregistered("rows".to_owned())]);
+    /// assert!(Range::Unregistered("rows".to_owned(), "10-20".to_owned()).is_supported_by(&accept));
+    /// assert!(!Range::bytes(0, 99).is_supported_by(&accept));
+    /// ```
+    pub fn is_supported_by(&self, accept_ranges: &AcceptRanges) -> bool {
+        accept_ranges.supports(&self.unit())
+    }
+
+    /// Resolve every byte range against the full length of the entity,
+    /// dropping those that are unsatisfiable.
+    ///
+    /// Returns an empty `Vec` for `Range::Unregistered`.
+    pub fn satisfiable_ranges(&self, full_length: u64) -> Vec<(u64, u64)> {
+        match *self {
+            Range::Bytes(ref specs) => specs.iter()
+                .filter_map(|spec| spec.to_satisfiable_range(full_length))
+                .collect(),
+            Range::Unregistered(..) => Vec::new(),
+        }
+    }
+
+    /// Compute the `Content-Length` of a `206 Partial Content` response to
+    /// this range, given the full length of the entity.
+    ///
+    /// For a single satisfiable range, this is the length of that range. For
+    /// several, it is the length of the whole `multipart/byteranges` body,
+    /// where each part carries the given `Content-Type` (if any) and its
+    /// `Content-Range`, laid out as:
+    ///
+    /// ```text
+    /// CRLF "--" boundary CRLF
+    /// [ "Content-Type: " content-type CRLF ]
+    /// "Content-Range: bytes " first "-" last "/" full-length CRLF
+    /// CRLF
+    /// part-data
+    /// ```
+    ///
+    /// and followed by the closing `CRLF "--" boundary "--" CRLF`.
+    ///
+    /// Returns `None` if no range is satisfiable, in which case the response
+    /// should be a `416 Range Not Satisfiable` (or a `200 OK` with the full
+    /// entity) instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::Range;
+    ///
+    /// let range = Range::bytes(0, 499);
+    /// assert_eq!(range.partial_content_length(8000, None, "boundary"), Some(500));
+    /// ```
+    pub fn partial_content_length(&self, full_length: u64, content_type: Option<&ContentType>,
+                                  boundary: &str) -> Option<u64> {
+        let ranges = self.satisfiable_ranges(full_length);
+        match ranges.len() {
+            0 => None,
+            1 => Some(ranges[0].1 - ranges[0].0 + 1),
+            _ => {
+                let content_type = content_type.map_or(0, |ct| {
+                    format!("{}: {}\r\n", ContentType::header_name(), ct).len() as u64
+                });
+                let parts = ranges.iter().map(|&(from, to)| {
+                    let content_range = ContentRange(ContentRangeSpec::Bytes {
+                        range: Some((from, to)),
+                        instance_length: Some(full_length),
+                    });
+                    let content_range = format!("{}: {}\r\n", ContentRange::header_name(),
+                                                content_range);
+                    // CRLF "--" boundary CRLF, headers, CRLF, data
+                    (2 + 2 + boundary.len() + 2) as u64 + content_type +
+                        content_range.len() as u64 + 2 + (to - from + 1)
+                }).sum::<u64>();
+                // CRLF "--" boundary "--" CRLF
+                Some(parts + (2 + 2 + boundary.len() + 2 + 2) as u64)
+            }
+        }
+    }
 }
 
 
@@ -276,7 +370,7 @@ impl Header for Range {
 #[cfg(test)]
 mod tests {
     use super::{ByteRangeSpec, Range};
-    use header::{Header, Raw};
+    use header::{AcceptRanges, Header, RangeUnit, Raw};
 
     #[test]
     fn test_parse_bytes_range_valid() {
@@ -315,6 +409,23 @@ mod tests {
 
     }
 
+    #[test]
+    fn test_custom_unit_supported() {
+        let w: Raw = "bytes, Rows".into();
+        let accept: AcceptRanges = Header::parse_header(&w).unwrap();
+
+        let w: Raw = "rows=10-20".into();
+        let r: Range = Header::parse_header(&w).unwrap();
+        assert_eq!(r.unit(), RangeUnit::Unregistered("rows".to_owned()));
+        assert!(r.is_supported_by(&accept));
+        assert!(Range::bytes(0, 10).is_supported_by(&accept));
+
+        let w: Raw = "pages=1-2".into();
+        let r: Range = Header::parse_header(&w).unwrap();
+        assert!(!r.is_supported_by(&accept));
+        assert!(!r.is_supported_by(&AcceptRanges(vec![RangeUnit::None])));
+    }
+
     #[test]
     fn test_parse_unregistered_range_valid() {
         let w: Raw = "custom=1-100,-100".into();
@@ -387,6 +498,50 @@ mod tests {
         assert_eq!(&headers.to_string(), "Range: custom=1-xxx\r\n");
     }
 
+    #[test]
+    fn test_partial_content_length_single() {
+        assert_eq!(Range::bytes(0, 499).partial_content_length(8000, None, "b"), Some(500));
+        assert_eq!(Range::bytes(7000, 9000).partial_content_length(8000, None, "b"), Some(1000));
+        let r = Range::Bytes(vec![ByteRangeSpec::Last(100)]);
+        assert_eq!(r.partial_content_length(8000, None, "b"), Some(100));
+        // an unsatisfiable range alongside a satisfiable one is dropped
+        let r = Range::Bytes(vec![ByteRangeSpec::AllFrom(9000), ByteRangeSpec::AllFrom(7500)]);
+        assert_eq!(r.partial_content_length(8000, None, "b"), Some(500));
+
+        assert_eq!(Range::bytes(8000, 9000).partial_content_length(8000, None, "b"), None);
+        let r = Range::Unregistered("custom".to_owned(), "1-2".to_owned());
+        assert_eq!(r.partial_content_length(8000, None, "b"), None);
+    }
+
+    #[test]
+    fn test_partial_content_length_multipart() {
+        use header::ContentType;
+
+        let body = format!(
+            "\r\n--THIS_STRING_SEPARATES\r\n\
+             Content-Type: application/pdf\r\n\
+             Content-Range: bytes 500-999/8000\r\n\
+             \r\n\
+             {}\
+             \r\n--THIS_STRING_SEPARATES\r\n\
+             Content-Type: application/pdf\r\n\
+             Content-Range: bytes 7000-7999/8000\r\n\
+             \r\n\
+             {}\
+             \r\n--THIS_STRING_SEPARATES--\r\n",
+            "a".repeat(500), "b".repeat(1000));
+        let pdf = ContentType("application/pdf".parse().unwrap());
+        let r = Range::bytes_multi(vec![(500, 999), (7000, 7999)]);
+        assert_eq!(r.partial_content_length(8000, Some(&pdf), "THIS_STRING_SEPARATES"),
+                   Some(body.len() as u64));
+
+        let body = "\r\n--x\r\nContent-Range: bytes 0-0/10\r\n\r\n0\
+                    \r\n--x\r\nContent-Range: bytes 9-9/10\r\n\r\n9\
+                    \r\n--x--\r\n";
+        let r = Range::Bytes(vec![ByteRangeSpec::FromTo(0, 0), ByteRangeSpec::Last(1)]);
+        assert_eq!(r.partial_content_length(10, None, "x"), Some(body.len() as u64));
+    }
+
     #[test]
     fn test_byte_range_spec_to_satisfiable_range() {
         assert_eq!(Some((0, 0)), ByteRangeSpec::FromTo(0, 0).to_satisfiable_range(3));
@@ -42,6 +42,8 @@ header! {
     }
 }
 
+__hyper__from_str!(Referer);
+
 bench_header!(bench, Referer, { vec![b"http://foo.com/hello:3000".to_vec()] });
 
 standard_header!(Referer, REFERER);
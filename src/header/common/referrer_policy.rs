@@ -34,7 +34,7 @@ use header::{Header, RawLike, parsing};
 /// let mut headers = Headers::new();
 /// headers.set(ReferrerPolicy::NoReferrer);
 /// ```
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
 pub enum ReferrerPolicy {
     /// `no-referrer`
     NoReferrer,
@@ -90,6 +90,8 @@ impl Header for ReferrerPolicy {
     }
 }
 
+__hyper__from_str!(ReferrerPolicy);
+
 impl fmt::Display for ReferrerPolicy {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::ReferrerPolicy::*;
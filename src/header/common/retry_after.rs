@@ -36,9 +36,10 @@
 // http://www.apache.org/licenses/
 
 use std::fmt;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use header::{Header, RawLike};
+use header::{Date, Header, Headers, RawLike};
+use header::parsing::{duration_to_secs_saturating, from_one_raw_str};
 use header::shared::HttpDate;
 
 /// The `Retry-After` header.
@@ -84,6 +85,95 @@ pub enum RetryAfter {
     DateTime(HttpDate),
 }
 
+impl RetryAfter {
+    /// Return the absolute time after which to retry.
+    ///
+    /// A `Delay` is relative to the time of the response, which is taken
+    /// from `date`, the value of the response's `Date` header, if present.
+    /// Otherwise the caller supplied `received` time is used, such as the
+    /// time the response was received. A `DateTime` is returned as is.
+    /// Returns `None` if a `Delay` ends after the year 9999, which an
+    /// `HttpDate` cannot represent.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use hyperx::header::{HttpDate, RetryAfter};
+    ///
+    /// let date: HttpDate = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+    /// let retry = RetryAfter::Delay(Duration::from_secs(120));
+    /// assert_eq!(
+    ///     retry.to_date(Some(date), SystemTime::now()).unwrap().to_string(),
+    ///     "Sun, 06 Nov 1994 08:51:37 GMT");
+    /// ```
+    pub fn to_date(&self, date: Option<HttpDate>, received: SystemTime) -> Option<HttpDate> {
+        // 9999-12-31T23:59:59Z
+        const MAX_SECS: u64 = 253_402_300_799;
+
+        match *self {
+            RetryAfter::Delay(delay) => {
+                let base = date.map(SystemTime::from).unwrap_or(received);
+                let base_secs = base.duration_since(UNIX_EPOCH)
+                    .map(|since| since.as_secs())
+                    .unwrap_or(0);
+                if base_secs.saturating_add(delay.as_secs()) > MAX_SECS {
+                    None
+                } else {
+                    Some(HttpDate::from(base + delay))
+                }
+            },
+            RetryAfter::DateTime(datetime) => Some(datetime),
+        }
+    }
+
+    /// Return the recommended time to retry, reconciling the `Retry-After`
+    /// and `RateLimit-Reset` headers of a response.
+    ///
+    /// Some APIs send both. `RateLimit-Reset`, from the IETF
+    /// [RateLimit header fields draft](https://tools.ietf.org/html/draft-ietf-httpapi-ratelimit-headers),
+    /// is the number of seconds until the rate limit quota resets. Delays of
+    /// either header are resolved as with `to_date`, relative to the `Date`
+    /// header if present or otherwise `received`. When both are present the
+    /// later time is returned, since a retry before either has passed is
+    /// likely to be refused again. Returns `None` if neither header is
+    /// present and valid, where a delay too large for `to_date` counts as
+    /// invalid.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::SystemTime;
+    /// use hyperx::header::{Headers, RetryAfter};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set_raw("Date", "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// headers.set_raw("Retry-After", "60");
+    /// headers.set_raw("RateLimit-Reset", "120");
+    /// assert_eq!(
+    ///     RetryAfter::retry_at(&headers, SystemTime::now()).unwrap().to_string(),
+    ///     "Sun, 06 Nov 1994 08:51:37 GMT");
+    /// ```
+    pub fn retry_at(headers: &Headers, received: SystemTime) -> Option<HttpDate> {
+        let date = headers.get::<Date>().map(|date| date.0);
+        let retry_after = headers.get::<RetryAfter>()
+            .and_then(|retry| retry.to_date(date, received));
+        let reset = headers.get_raw("RateLimit-Reset")
+            .and_then(|raw| from_one_raw_str::<_, u64>(raw).ok())
+            .and_then(|secs| RetryAfter::Delay(Duration::from_secs(secs)).to_date(date, received));
+        match (retry_after, reset) {
+            (Some(a), Some(b)) => Some(::std::cmp::max(a, b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+impl From<Duration> for RetryAfter {
+    /// A `Delay` of whole seconds, converted with
+    /// `duration_to_secs_saturating`.
+    fn from(delay: Duration) -> RetryAfter {
+        RetryAfter::Delay(Duration::from_secs(u64::from(duration_to_secs_saturating(delay))))
+    }
+}
+
 impl Header for RetryAfter {
     fn header_name() -> &'static str {
         static NAME: &'static str = "Retry-After";
@@ -118,6 +208,8 @@ impl Header for RetryAfter {
     }
 }
 
+__hyper__from_str!(RetryAfter);
+
 impl fmt::Display for RetryAfter {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -133,8 +225,8 @@ impl fmt::Display for RetryAfter {
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
-    use header::{Header, Raw};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use header::{Header, Headers, Raw};
     use header::shared::HttpDate;
 
     use super::RetryAfter;
@@ -181,6 +273,79 @@ mod tests {
         let expected = "Sun, 06 Nov 1994 08:49:37 GMT".parse::<HttpDate>().unwrap();
         assert_eq!(retry_after, RetryAfter::DateTime(expected));
     }
+
+    #[test]
+    fn to_date_delay_from_date() {
+        let date = "Sun, 06 Nov 1994 08:49:37 GMT".parse::<HttpDate>().unwrap();
+        let retry_after = RetryAfter::Delay(Duration::from_secs(300));
+        let at = retry_after.to_date(Some(date), SystemTime::now()).unwrap();
+        assert_eq!(at.to_string(), "Sun, 06 Nov 1994 08:54:37 GMT");
+    }
+
+    #[test]
+    fn to_date_delay_from_received() {
+        // Sun, 06 Nov 1994 08:49:37 GMT
+        let received = UNIX_EPOCH + Duration::from_secs(784111777);
+        let retry_after = RetryAfter::Delay(Duration::from_secs(60));
+        let at = retry_after.to_date(None, received).unwrap();
+        assert_eq!(at.to_string(), "Sun, 06 Nov 1994 08:50:37 GMT");
+    }
+
+    #[test]
+    fn to_date_datetime() {
+        let dt = "Sun, 06 Nov 1994 08:49:37 GMT".parse::<HttpDate>().unwrap();
+        let retry_after = RetryAfter::DateTime(dt);
+        assert_eq!(retry_after.to_date(None, SystemTime::now()), Some(dt));
+    }
+
+    #[test]
+    fn retry_at_both() {
+        let mut headers = Headers::new();
+        headers.set_raw("Date", "Sun, 06 Nov 1994 08:49:37 GMT");
+        headers.set_raw("Retry-After", "Sun, 06 Nov 1994 08:55:00 GMT");
+        headers.set_raw("RateLimit-Reset", "60");
+        let at = RetryAfter::retry_at(&headers, SystemTime::now()).unwrap();
+        assert_eq!(at.to_string(), "Sun, 06 Nov 1994 08:55:00 GMT");
+
+        headers.set_raw("RateLimit-Reset", "600");
+        let at = RetryAfter::retry_at(&headers, SystemTime::now()).unwrap();
+        assert_eq!(at.to_string(), "Sun, 06 Nov 1994 08:59:37 GMT");
+    }
+
+    #[test]
+    fn retry_at_one() {
+        // Sun, 06 Nov 1994 08:49:37 GMT
+        let received = UNIX_EPOCH + Duration::from_secs(784111777);
+        let mut headers = Headers::new();
+        assert_eq!(RetryAfter::retry_at(&headers, received), None);
+
+        headers.set_raw("RateLimit-Reset", "30");
+        let at = RetryAfter::retry_at(&headers, received).unwrap();
+        assert_eq!(at.to_string(), "Sun, 06 Nov 1994 08:50:07 GMT");
+
+        headers.remove_raw("RateLimit-Reset");
+        headers.set(RetryAfter::Delay(Duration::from_secs(90)));
+        let at = RetryAfter::retry_at(&headers, received).unwrap();
+        assert_eq!(at.to_string(), "Sun, 06 Nov 1994 08:51:07 GMT");
+
+        headers.set_raw("RateLimit-Reset", "soon");
+        assert_eq!(RetryAfter::retry_at(&headers, received), Some(at));
+    }
+
+    #[test]
+    fn retry_at_overflow() {
+        let mut headers = Headers::new();
+        headers.set_raw("Date", "Sun, 06 Nov 1994 08:49:37 GMT");
+        headers.set_raw("RateLimit-Reset", "18446744073709551615");
+        assert_eq!(RetryAfter::retry_at(&headers, SystemTime::now()), None);
+
+        headers.set_raw("Retry-After", "60");
+        let at = RetryAfter::retry_at(&headers, SystemTime::now()).unwrap();
+        assert_eq!(at.to_string(), "Sun, 06 Nov 1994 08:50:37 GMT");
+
+        let delay = RetryAfter::Delay(Duration::from_secs(::std::u64::MAX));
+        assert_eq!(delay.to_date(None, SystemTime::now()), None);
+    }
 }
 
 standard_header!(RetryAfter, RETRY_AFTER);
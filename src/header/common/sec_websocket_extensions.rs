@@ -0,0 +1,218 @@
+use std::fmt;
+use std::str::FromStr;
+
+use header::parsing::{is_token, split_unquoted, unescape};
+
+header! {
+    /// `Sec-WebSocket-Extensions` header, defined in
+    /// [RFC6455](https://tools.ietf.org/html/rfc6455#section-11.3.2)
+    ///
+    /// The `Sec-WebSocket-Extensions` header field is sent by a client to
+    /// offer WebSocket extensions, in order of preference, and by the server
+    /// to select the extensions in use, such as `permessage-deflate` from
+    /// [RFC7692](https://tools.ietf.org/html/rfc7692).
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Sec-WebSocket-Extensions = extension-list
+    /// extension-list = 1#extension
+    /// extension = extension-token *( ";" extension-param )
+    /// extension-token = registered-token
+    /// registered-token = token
+    /// extension-param = token [ "=" (token | quoted-string) ]
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `permessage-deflate`
+    /// * `permessage-deflate; client_max_window_bits, permessage-deflate; server_no_context_takeover`
+    /// * `permessage-deflate; server_no_context_takeover; client_max_window_bits=10`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, SecWebSocketExtensions, WebSocketExtension};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(SecWebSocketExtensions(vec![
+    ///     WebSocketExtension::new("permessage-deflate")
+    ///         .with_param("client_max_window_bits", None),
+    /// ]));
+    /// ```
+    (SecWebSocketExtensions, "Sec-WebSocket-Extensions") => (WebSocketExtension)+
+
+    test_sec_websocket_extensions {
+        test_header!(test1, vec![b"permessage-deflate"]);
+        test_header!(
+            test_offer,
+            vec![b"permessage-deflate; client_max_window_bits; server_max_window_bits=10, \
+                   permessage-deflate; client_max_window_bits"],
+            Some(HeaderField(vec![
+                WebSocketExtension::new("permessage-deflate")
+                    .with_param("client_max_window_bits", None)
+                    .with_param("server_max_window_bits", Some("10")),
+                WebSocketExtension::new("permessage-deflate")
+                    .with_param("client_max_window_bits", None),
+            ])));
+        test_header!(
+            test_quoted,
+            vec![b"x-ext; note=\"a, \\\"b\\\"\""],
+            Some(HeaderField(vec![
+                WebSocketExtension::new("x-ext").with_param("note", Some("a, \"b\"")),
+            ])));
+        test_header!(
+            test_quoted_semicolon,
+            vec![b"x-ext; note=\"a;b\"; mode=1"],
+            Some(HeaderField(vec![
+                WebSocketExtension::new("x-ext")
+                    .with_param("note", Some("a;b"))
+                    .with_param("mode", Some("1")),
+            ])));
+
+        #[test]
+        fn test_negotiated() {
+            let r: Raw = "permessage-deflate; server_no_context_takeover; client_max_window_bits=10".into();
+            let exts = HeaderField::parse_header(&r).unwrap();
+            let deflate = exts.permessage_deflate().unwrap();
+            assert!(deflate.has_param("server_no_context_takeover"));
+            assert!(!deflate.has_param("client_no_context_takeover"));
+            assert_eq!(deflate.client_max_window_bits(), Some(10));
+            assert_eq!(deflate.server_max_window_bits(), None);
+        }
+
+        #[test]
+        fn test_window_bits() {
+            let r: Raw = "x-other, PerMessage-Deflate; client_max_window_bits; server_max_window_bits=\"15\"".into();
+            let exts = HeaderField::parse_header(&r).unwrap();
+            let deflate = exts.permessage_deflate().unwrap();
+            assert!(deflate.has_param("client_max_window_bits"));
+            assert_eq!(deflate.client_max_window_bits(), None);
+            assert_eq!(deflate.server_max_window_bits(), Some(15));
+
+            let r: Raw = "permessage-deflate; server_max_window_bits=16".into();
+            let exts = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(exts.permessage_deflate().unwrap().server_max_window_bits(), None);
+
+            let r: Raw = "x-other".into();
+            assert!(HeaderField::parse_header(&r).unwrap().permessage_deflate().is_none());
+        }
+    }
+}
+
+__hyper__list!(SecWebSocketExtensions => WebSocketExtension);
+__hyper__from_str!(SecWebSocketExtensions);
+
+impl SecWebSocketExtensions {
+    /// Get the first `permessage-deflate` extension, if any.
+    pub fn permessage_deflate(&self) -> Option<&WebSocketExtension> {
+        self.iter().find(|ext| ext.name.eq_ignore_ascii_case("permessage-deflate"))
+    }
+}
+
+/// An extension of the `Sec-WebSocket-Extensions` header, with its
+/// parameters.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct WebSocketExtension {
+    /// The extension name, such as `permessage-deflate`.
+    pub name: String,
+    /// The parameters, each a name and an optional value.
+    pub params: Vec<(String, Option<String>)>,
+}
+
+impl WebSocketExtension {
+    /// Create an extension without parameters.
+    pub fn new<N: Into<String>>(name: N) -> WebSocketExtension {
+        WebSocketExtension {
+            name: name.into(),
+            params: Vec::new(),
+        }
+    }
+
+    /// Add a parameter, returning the extension.
+    pub fn with_param<N: Into<String>>(mut self, name: N, value: Option<&str>) -> WebSocketExtension {
+        self.params.push((name.into(), value.map(ToOwned::to_owned)));
+        self
+    }
+
+    /// Returns true if the parameter is present, with or without a value.
+    pub fn has_param(&self, name: &str) -> bool {
+        self.params.iter().any(|&(ref n, _)| n.eq_ignore_ascii_case(name))
+    }
+
+    /// Get the value of a parameter, if present with a value.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter()
+            .find(|&&(ref n, _)| n.eq_ignore_ascii_case(name))
+            .and_then(|&(_, ref v)| v.as_ref().map(|v| &v[..]))
+    }
+
+    /// Get the `client_max_window_bits` of a `permessage-deflate` extension,
+    /// if given a valid value from 8 to 15.
+    pub fn client_max_window_bits(&self) -> Option<u8> {
+        self.window_bits("client_max_window_bits")
+    }
+
+    /// Get the `server_max_window_bits` of a `permessage-deflate` extension,
+    /// if given a valid value from 8 to 15.
+    pub fn server_max_window_bits(&self) -> Option<u8> {
+        self.window_bits("server_max_window_bits")
+    }
+
+    fn window_bits(&self, name: &str) -> Option<u8> {
+        self.param(name)
+            .and_then(|v| v.parse().ok())
+            .and_then(|bits| if bits >= 8 && bits <= 15 { Some(bits) } else { None })
+    }
+}
+
+impl FromStr for WebSocketExtension {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> ::Result<WebSocketExtension> {
+        let mut parts = split_unquoted(s, b';').map(str::trim);
+        let name = parts.next().unwrap_or("");
+        if !is_token(name) {
+            return Err(::Error::Header);
+        }
+        let mut ext = WebSocketExtension::new(name);
+        for param in parts {
+            let mut kv = param.splitn(2, '=').map(str::trim);
+            let key = kv.next().unwrap_or("");
+            if !is_token(key) {
+                return Err(::Error::Header);
+            }
+            let value = match kv.next() {
+                Some(v) if v.len() >= 2 && v.starts_with('"') && v.ends_with('"') => {
+                    Some(unescape(&v[1..v.len() - 1]))
+                },
+                Some(v) if is_token(v) => Some(v.to_owned()),
+                Some(_) => return Err(::Error::Header),
+                None => None,
+            };
+            ext.params.push((key.to_owned(), value));
+        }
+        Ok(ext)
+    }
+}
+
+impl fmt::Display for WebSocketExtension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(f.write_str(&self.name));
+        for &(ref name, ref value) in &self.params {
+            try!(write!(f, "; {}", name));
+            match *value {
+                Some(ref v) if is_token(v) => try!(write!(f, "={}", v)),
+                Some(ref v) => try!(write!(f, "=\"{}\"", v.replace("\\", "\\\\").replace("\"", "\\\""))),
+                None => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+bench_header!(bench, SecWebSocketExtensions, {
+    vec![b"permessage-deflate; client_max_window_bits, permessage-deflate".to_vec()]
+});
+
+standard_header!(SecWebSocketExtensions, SEC_WEBSOCKET_EXTENSIONS);
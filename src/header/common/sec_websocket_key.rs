@@ -0,0 +1,190 @@
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+
+use base64;
+use header::{Connection, Headers, ProtocolName, SecWebSocketVersion, Upgrade};
+
+header! {
+    /// `Sec-WebSocket-Key` header, defined in
+    /// [RFC6455](https://tools.ietf.org/html/rfc6455#section-11.3.1)
+    ///
+    /// The `Sec-WebSocket-Key` header field is sent by a client in the
+    /// opening handshake. It carries a randomly selected 16 byte nonce, base64
+    /// encoded, from which the server computes its `Sec-WebSocket-Accept`
+    /// value.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Sec-WebSocket-Key = base64-value-non-empty
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `dGhlIHNhbXBsZSBub25jZQ==`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, SecWebSocketKey};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(SecWebSocketKey("dGhlIHNhbXBsZSBub25jZQ==".to_owned()));
+    /// ```
+    (SecWebSocketKey, "Sec-WebSocket-Key") => [String]
+
+    test_sec_websocket_key {
+        test_header!(test1, vec![b"dGhlIHNhbXBsZSBub25jZQ=="]);
+
+        #[test]
+        fn test_is_valid() {
+            assert!(HeaderField("dGhlIHNhbXBsZSBub25jZQ==".to_owned()).is_valid());
+            assert!(!HeaderField("".to_owned()).is_valid());
+            assert!(!HeaderField("dGhlIHNhbXBsZSBub25j".to_owned()).is_valid());
+            assert!(!HeaderField("dGhlIHNhbXBsZSBub25jZQ".to_owned()).is_valid());
+            assert!(!HeaderField("not base64, not at all!!".to_owned()).is_valid());
+        }
+
+        #[test]
+        fn test_valid_handshake() {
+            let headers = handshake();
+            assert_eq!(validate_websocket_handshake(&headers),
+                       Ok(HeaderField("dGhlIHNhbXBsZSBub25jZQ==".to_owned())));
+
+            let mut headers = handshake();
+            headers.set_raw("Upgrade", "WebSocket");
+            headers.set_raw("Connection", "keep-alive, Upgrade");
+            assert!(validate_websocket_handshake(&headers).is_ok());
+        }
+
+        #[test]
+        fn test_invalid_handshake() {
+            let mut headers = handshake();
+            headers.remove::<Upgrade>();
+            assert_eq!(validate_websocket_handshake(&headers), Err(HandshakeError::NotWebSocket));
+            headers.set_raw("Upgrade", "h2c");
+            assert_eq!(validate_websocket_handshake(&headers), Err(HandshakeError::NotWebSocket));
+
+            let mut headers = handshake();
+            headers.set(Connection::keep_alive());
+            assert_eq!(validate_websocket_handshake(&headers),
+                       Err(HandshakeError::MissingConnectionUpgrade));
+
+            let mut headers = handshake();
+            headers.remove::<SecWebSocketVersion>();
+            assert_eq!(validate_websocket_handshake(&headers),
+                       Err(HandshakeError::UnsupportedVersion));
+            headers.set(SecWebSocketVersion(8));
+            assert_eq!(validate_websocket_handshake(&headers),
+                       Err(HandshakeError::UnsupportedVersion));
+
+            let mut headers = handshake();
+            headers.remove::<HeaderField>();
+            assert_eq!(validate_websocket_handshake(&headers), Err(HandshakeError::MissingKey));
+            headers.set(HeaderField("c2hvcnQ=".to_owned()));
+            assert_eq!(validate_websocket_handshake(&headers), Err(HandshakeError::InvalidKey));
+        }
+
+        fn handshake() -> Headers {
+            let mut headers = Headers::new();
+            headers.set_raw("Host", "server.example.com");
+            headers.set_raw("Upgrade", "websocket");
+            headers.set_raw("Connection", "Upgrade");
+            headers.set_raw("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ==");
+            headers.set_raw("Sec-WebSocket-Version", "13");
+            headers
+        }
+    }
+}
+
+__hyper__from_str!(SecWebSocketKey);
+
+impl SecWebSocketKey {
+    /// Returns true if the key is the base64 encoding of 16 bytes, as RFC6455
+    /// requires.
+    pub fn is_valid(&self) -> bool {
+        self.0.len() == 24 && base64::decode(&self.0).map(|nonce| nonce.len() == 16).unwrap_or(false)
+    }
+}
+
+/// Check the headers of a WebSocket opening handshake request, defined in
+/// [RFC6455](https://tools.ietf.org/html/rfc6455#section-4.2.1), returning
+/// the `Sec-WebSocket-Key` from which to compute the `Sec-WebSocket-Accept`
+/// response.
+///
+/// This checks for `Upgrade: websocket`, the `upgrade` option of
+/// `Connection`, `Sec-WebSocket-Version: 13` and a valid `Sec-WebSocket-Key`.
+/// The request method, which must be `GET`, is not part of the headers and
+/// is left to the caller.
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{validate_websocket_handshake, Headers, HandshakeError};
+///
+/// let mut headers = Headers::new();
+/// headers.set_raw("Upgrade", "websocket");
+/// headers.set_raw("Connection", "Upgrade");
+/// headers.set_raw("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ==");
+/// assert_eq!(validate_websocket_handshake(&headers), Err(HandshakeError::UnsupportedVersion));
+///
+/// headers.set_raw("Sec-WebSocket-Version", "13");
+/// let key = validate_websocket_handshake(&headers).unwrap();
+/// assert_eq!(*key, "dGhlIHNhbXBsZSBub25jZQ==");
+/// ```
+pub fn validate_websocket_handshake(req: &Headers) -> Result<SecWebSocketKey, HandshakeError> {
+    let websocket = req.get::<Upgrade>()
+        .map_or(false, |upgrade| upgrade.iter().any(|p| p.name == ProtocolName::WebSocket));
+    if !websocket {
+        return Err(HandshakeError::NotWebSocket);
+    }
+    let connection_upgrade = req.get::<Connection>()
+        .map_or(false, |conn| conn.contains_header_name("upgrade"));
+    if !connection_upgrade {
+        return Err(HandshakeError::MissingConnectionUpgrade);
+    }
+    if req.get::<SecWebSocketVersion>() != Some(&SecWebSocketVersion::V13) {
+        return Err(HandshakeError::UnsupportedVersion);
+    }
+    match req.get::<SecWebSocketKey>() {
+        Some(key) if key.is_valid() => Ok(key.clone()),
+        Some(_) => Err(HandshakeError::InvalidKey),
+        None => Err(HandshakeError::MissingKey),
+    }
+}
+
+/// An invalid WebSocket opening handshake, as returned by
+/// `validate_websocket_handshake`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HandshakeError {
+    /// `Upgrade` is absent or does not include `websocket`.
+    NotWebSocket,
+    /// `Connection` does not list `upgrade`.
+    MissingConnectionUpgrade,
+    /// `Sec-WebSocket-Version` is absent or not `13`. The server should
+    /// respond with `426 Upgrade Required` and `Sec-WebSocket-Version: 13`.
+    UnsupportedVersion,
+    /// `Sec-WebSocket-Key` is absent.
+    MissingKey,
+    /// `Sec-WebSocket-Key` is not the base64 encoding of 16 bytes.
+    InvalidKey,
+}
+
+impl Display for HandshakeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            HandshakeError::NotWebSocket => "Upgrade header does not include websocket",
+            HandshakeError::MissingConnectionUpgrade =>
+                "Connection: upgrade option missing from WebSocket handshake",
+            HandshakeError::UnsupportedVersion => "Sec-WebSocket-Version is not 13",
+            HandshakeError::MissingKey => "Sec-WebSocket-Key header missing",
+            HandshakeError::InvalidKey => "Sec-WebSocket-Key is not a base64 encoded 16 byte nonce",
+        })
+    }
+}
+
+impl StdError for HandshakeError {}
+
+bench_header!(bench, SecWebSocketKey, { vec![b"dGhlIHNhbXBsZSBub25jZQ==".to_vec()] });
+
+standard_header!(SecWebSocketKey, SEC_WEBSOCKET_KEY);
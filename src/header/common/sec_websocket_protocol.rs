@@ -0,0 +1,80 @@
+header! {
+    /// `Sec-WebSocket-Protocol` header, defined in
+    /// [RFC6455](https://tools.ietf.org/html/rfc6455#section-11.3.4)
+    ///
+    /// The `Sec-WebSocket-Protocol` header field is sent by a client in the
+    /// opening handshake to offer subprotocols, in order of preference, and
+    /// by the server to select exactly one of them.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Sec-WebSocket-Protocol-Client = 1#token
+    /// Sec-WebSocket-Protocol-Server = token
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `chat, superchat`
+    /// * `graphql-transport-ws`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, SecWebSocketProtocol};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(SecWebSocketProtocol(vec!["chat".to_owned(), "superchat".to_owned()]));
+    /// ```
+    (SecWebSocketProtocol, "Sec-WebSocket-Protocol") => (String)+
+
+    test_sec_websocket_protocol {
+        test_header!(test1, vec![b"chat, superchat"]);
+        test_header!(test2, vec!["chat".as_bytes(), "superchat".as_bytes()],
+            Some(HeaderField(vec!["chat".to_owned(), "superchat".to_owned()])));
+
+        #[test]
+        fn test_select_subprotocol() {
+            let offered = HeaderField(vec!["v2.chat".to_owned(), "chat".to_owned()]);
+            assert_eq!(select_subprotocol(&offered, &["chat", "v2.chat"]),
+                       Some("v2.chat".to_owned()));
+            assert_eq!(select_subprotocol(&offered, &["chat"]), Some("chat".to_owned()));
+        }
+
+        #[test]
+        fn test_select_subprotocol_none() {
+            let offered = HeaderField(vec!["chat".to_owned()]);
+            assert_eq!(select_subprotocol(&offered, &["mqtt", "Chat"]), None);
+            assert_eq!(select_subprotocol(&offered, &[]), None);
+        }
+    }
+}
+
+__hyper__list!(SecWebSocketProtocol => String);
+__hyper__from_str!(SecWebSocketProtocol);
+
+/// Select the subprotocol for a server to echo in its
+/// `Sec-WebSocket-Protocol` response, as the first one `offered` by the
+/// client which is also `supported`.
+///
+/// The client lists subprotocols in order of preference, so its order takes
+/// precedence over that of `supported`. Names are compared exactly. If
+/// `None` is returned, the server should not send `Sec-WebSocket-Protocol`.
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{select_subprotocol, SecWebSocketProtocol};
+///
+/// let offered = SecWebSocketProtocol(vec!["chat".to_owned(), "superchat".to_owned()]);
+/// assert_eq!(select_subprotocol(&offered, &["superchat", "chat"]), Some("chat".to_owned()));
+/// ```
+pub fn select_subprotocol(offered: &SecWebSocketProtocol, supported: &[&str]) -> Option<String> {
+    offered.iter()
+        .find(|protocol| supported.contains(&protocol.as_str()))
+        .cloned()
+}
+
+bench_header!(bench, SecWebSocketProtocol, { vec![b"chat, superchat".to_vec()] });
+
+standard_header!(SecWebSocketProtocol, SEC_WEBSOCKET_PROTOCOL);
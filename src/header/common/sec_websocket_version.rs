@@ -0,0 +1,48 @@
+header! {
+    /// `Sec-WebSocket-Version` header, defined in
+    /// [RFC6455](https://tools.ietf.org/html/rfc6455#section-11.3.5)
+    ///
+    /// The `Sec-WebSocket-Version` header field is sent by a client in the
+    /// opening handshake to indicate the version of the WebSocket protocol it
+    /// wishes to use. The only version defined by RFC6455 is `13`.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Sec-WebSocket-Version-Client = version
+    /// version = DIGIT | (NZDIGIT DIGIT) |
+    ///           ("1" DIGIT DIGIT) | ("2" DIGIT DIGIT)
+    ///           ; Limited to 0-255 range, with no leading zeros
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `13`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, SecWebSocketVersion};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(SecWebSocketVersion::V13);
+    /// ```
+    (SecWebSocketVersion, "Sec-WebSocket-Version") => [u8]
+
+    test_sec_websocket_version {
+        test_header!(test1, vec![b"13"], Some(HeaderField(13)));
+        test_header!(test2, vec![b"256"], None::<HeaderField>);
+        test_header!(test3, vec![b"13, 8"], None::<HeaderField>);
+    }
+}
+
+__hyper__from_str!(SecWebSocketVersion);
+
+impl SecWebSocketVersion {
+    /// Version 13, as defined by RFC6455.
+    pub const V13: SecWebSocketVersion = SecWebSocketVersion(13);
+}
+
+bench_header!(bench, SecWebSocketVersion, { vec![b"13".to_vec()] });
+
+standard_header!(SecWebSocketVersion, SEC_WEBSOCKET_VERSION);
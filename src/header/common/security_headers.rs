@@ -0,0 +1,198 @@
+use std::fmt;
+
+use header::{ContentSecurityPolicy, CspDirective, CspSource, Headers, ReferrerPolicy,
+             StrictTransportSecurity};
+
+/// A builder of a recommended set of security response headers.
+///
+/// By default this sets:
+///
+/// * `Strict-Transport-Security: max-age=31536000; includeSubDomains`
+/// * `X-Content-Type-Options: nosniff`
+/// * `X-Frame-Options: DENY`
+/// * `Referrer-Policy: strict-origin-when-cross-origin`
+/// * `Content-Security-Policy: default-src 'self'; object-src 'none';
+///   base-uri 'self'; frame-ancestors 'none'`
+///
+/// Each may be replaced, or omitted by passing `None` or `false`.
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{FrameOptions, Headers, ReferrerPolicy, SecurityHeaders};
+///
+/// let security = SecurityHeaders::new()
+///     .frame_options(Some(FrameOptions::SameOrigin))
+///     .referrer_policy(Some(ReferrerPolicy::NoReferrer));
+/// let mut headers = Headers::new();
+/// security.apply(&mut headers);
+/// assert_eq!(headers.get_raw("X-Frame-Options").unwrap(), "SAMEORIGIN");
+/// assert_eq!(headers.get::<ReferrerPolicy>(), Some(&ReferrerPolicy::NoReferrer));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SecurityHeaders {
+    strict_transport_security: Option<StrictTransportSecurity>,
+    nosniff: bool,
+    frame_options: Option<FrameOptions>,
+    referrer_policy: Option<ReferrerPolicy>,
+    content_security_policy: Option<ContentSecurityPolicy>,
+}
+
+/// A value of the `X-Frame-Options` header, as set by `SecurityHeaders`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum FrameOptions {
+    /// `DENY`, the document may not be framed.
+    Deny,
+    /// `SAMEORIGIN`, the document may only be framed by its own origin.
+    SameOrigin,
+}
+
+impl fmt::Display for FrameOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            FrameOptions::Deny => "DENY",
+            FrameOptions::SameOrigin => "SAMEORIGIN",
+        })
+    }
+}
+
+impl SecurityHeaders {
+    /// Create a builder with the recommended defaults.
+    pub fn new() -> SecurityHeaders {
+        SecurityHeaders {
+            strict_transport_security: Some(
+                StrictTransportSecurity::including_subdomains(31_536_000)),
+            nosniff: true,
+            frame_options: Some(FrameOptions::Deny),
+            referrer_policy: Some(ReferrerPolicy::StrictOriginWhenCrossOrigin),
+            content_security_policy: Some(ContentSecurityPolicy(vec![
+                CspDirective::new("default-src", vec![CspSource::Self_]),
+                CspDirective::new("object-src", vec![CspSource::None_]),
+                CspDirective::new("base-uri", vec![CspSource::Self_]),
+                CspDirective::new("frame-ancestors", vec![CspSource::None_]),
+            ])),
+        }
+    }
+
+    /// Set or omit `Strict-Transport-Security`.
+    pub fn strict_transport_security(mut self, sts: Option<StrictTransportSecurity>)
+        -> SecurityHeaders
+    {
+        self.strict_transport_security = sts;
+        self
+    }
+
+    /// Set or omit `X-Content-Type-Options: nosniff`.
+    pub fn nosniff(mut self, nosniff: bool) -> SecurityHeaders {
+        self.nosniff = nosniff;
+        self
+    }
+
+    /// Set or omit `X-Frame-Options`.
+    pub fn frame_options(mut self, frame_options: Option<FrameOptions>) -> SecurityHeaders {
+        self.frame_options = frame_options;
+        self
+    }
+
+    /// Set or omit `Referrer-Policy`.
+    pub fn referrer_policy(mut self, policy: Option<ReferrerPolicy>) -> SecurityHeaders {
+        self.referrer_policy = policy;
+        self
+    }
+
+    /// Set or omit `Content-Security-Policy`.
+    pub fn content_security_policy(mut self, csp: Option<ContentSecurityPolicy>)
+        -> SecurityHeaders
+    {
+        self.content_security_policy = csp;
+        self
+    }
+
+    /// Set the headers, replacing any of the same name already present.
+    ///
+    /// Headers which have been omitted are left as they are.
+    pub fn apply(&self, headers: &mut Headers) {
+        if let Some(ref sts) = self.strict_transport_security {
+            headers.set(sts.clone());
+        }
+        if self.nosniff {
+            headers.set_raw("X-Content-Type-Options", "nosniff");
+        }
+        if let Some(frame_options) = self.frame_options {
+            headers.set_raw("X-Frame-Options", frame_options.to_string());
+        }
+        if let Some(ref policy) = self.referrer_policy {
+            headers.set(policy.clone());
+        }
+        if let Some(ref csp) = self.content_security_policy {
+            headers.set(csp.clone());
+        }
+    }
+
+    /// Return a new `Headers` with just these headers.
+    pub fn to_headers(&self) -> Headers {
+        let mut headers = Headers::new();
+        self.apply(&mut headers);
+        headers
+    }
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> SecurityHeaders {
+        SecurityHeaders::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameOptions, SecurityHeaders};
+    use header::{ContentSecurityPolicy, CspDirective, CspSource, ReferrerPolicy,
+                 StrictTransportSecurity};
+
+    #[test]
+    fn test_defaults() {
+        let headers = SecurityHeaders::new().to_headers();
+        let mut names = headers.sorted_iter().map(|h| h.name()).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec![
+            "Content-Security-Policy", "Referrer-Policy", "Strict-Transport-Security",
+            "X-Content-Type-Options", "X-Frame-Options",
+        ]);
+        assert_eq!(headers.get(),
+                   Some(&StrictTransportSecurity::including_subdomains(31536000)));
+        assert_eq!(headers.get_raw("X-Content-Type-Options").unwrap(), "nosniff");
+        assert_eq!(headers.get_raw("X-Frame-Options").unwrap(), "DENY");
+        assert_eq!(headers.get(), Some(&ReferrerPolicy::StrictOriginWhenCrossOrigin));
+        assert_eq!(headers.get::<ContentSecurityPolicy>().unwrap().to_string(),
+                   "default-src 'self'; object-src 'none'; base-uri 'self'; \
+                    frame-ancestors 'none'");
+    }
+
+    #[test]
+    fn test_overrides() {
+        let csp = ContentSecurityPolicy(vec![
+            CspDirective::new("default-src", vec![
+                CspSource::Self_,
+                CspSource::Scheme("https".to_owned()),
+            ]),
+        ]);
+        let headers = SecurityHeaders::default()
+            .strict_transport_security(Some(StrictTransportSecurity::excluding_subdomains(60)))
+            .nosniff(false)
+            .frame_options(None)
+            .referrer_policy(Some(ReferrerPolicy::NoReferrer))
+            .content_security_policy(Some(csp.clone()))
+            .to_headers();
+        assert_eq!(headers.len(), 3);
+        assert_eq!(headers.get(), Some(&StrictTransportSecurity::excluding_subdomains(60)));
+        assert!(headers.get_raw("X-Content-Type-Options").is_none());
+        assert!(headers.get_raw("X-Frame-Options").is_none());
+        assert_eq!(headers.get(), Some(&ReferrerPolicy::NoReferrer));
+        assert_eq!(headers.get(), Some(&csp));
+
+        let headers = SecurityHeaders::new()
+            .frame_options(Some(FrameOptions::SameOrigin))
+            .to_headers();
+        assert_eq!(headers.get_raw("X-Frame-Options").unwrap(), "SAMEORIGIN");
+    }
+}
@@ -35,6 +35,121 @@ header! {
     }
 }
 
+__hyper__from_str!(Server);
+
+impl Server {
+    /// Return a copy of this `Server` with the version of every product
+    /// removed, leaving product names and comments intact.
+    ///
+    /// Useful for proxies that forward or log the header but don't want to
+    /// leak details of the software stack behind them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::Server;
+    ///
+    /// let server = Server::new("Apache/2.4.1 (Unix) OpenSSL/1.0.2k");
+    /// assert_eq!(&*server.redact_versions(), "Apache (Unix) OpenSSL");
+    /// ```
+    pub fn redact_versions(&self) -> Server {
+        Server::new(redact_versions(self))
+    }
+
+    /// Return a copy of this `Server` with all comments removed, including
+    /// nested ones, leaving the products.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::Server;
+    ///
+    /// let server = Server::new("Apache/2.4.1 (Unix (internal)) OpenSSL/1.0.2k");
+    /// assert_eq!(&*server.strip_comments(), "Apache/2.4.1 OpenSSL/1.0.2k");
+    /// ```
+    pub fn strip_comments(&self) -> Server {
+        Server::new(strip_comments(self))
+    }
+}
+
+/// Split a `product *( RWS ( product / comment ) )` value into its products
+/// and (possibly nested) comments.
+fn elements(s: &str) -> Vec<&str> {
+    let mut elements = Vec::new();
+    let mut iter = s.char_indices().peekable();
+    while let Some((start, c)) = iter.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut end = s.len();
+        if c == '(' {
+            let mut depth = 1;
+            while let Some((i, c)) = iter.next() {
+                match c {
+                    '\\' => { iter.next(); },
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            end = i + 1;
+                            break;
+                        }
+                    },
+                    _ => (),
+                }
+            }
+        } else {
+            while let Some(&(i, c)) = iter.peek() {
+                if c.is_whitespace() || c == '(' {
+                    end = i;
+                    break;
+                }
+                iter.next();
+            }
+        }
+        elements.push(&s[start..end]);
+    }
+    elements
+}
+
+fn redact_versions(s: &str) -> String {
+    elements(s).into_iter()
+        .map(|e| if e.starts_with('(') { e } else { e.split('/').next().unwrap_or(e) })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn strip_comments(s: &str) -> String {
+    elements(s).into_iter()
+        .filter(|e| !e.starts_with('('))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Server;
+
+    #[test]
+    fn test_redact_versions() {
+        let server = Server::new("CERN/3.0 libwww/2.17");
+        assert_eq!(&*server.redact_versions(), "CERN libwww");
+
+        let server = Server::new("nginx");
+        assert_eq!(&*server.redact_versions(), "nginx");
+
+        let server = Server::new("Apache/2.4.1 (Unix; build/7 \\) (x)) mod_ssl/2.4.1");
+        assert_eq!(&*server.redact_versions(), "Apache (Unix; build/7 \\) (x)) mod_ssl");
+    }
+
+    #[test]
+    fn test_strip_comments() {
+        let server = Server::new("Apache/2.4.1 (Unix (internal)) OpenSSL/1.0.2k(debug)");
+        assert_eq!(&*server.strip_comments(), "Apache/2.4.1 OpenSSL/1.0.2k");
+        assert_eq!(&*server.strip_comments().redact_versions(), "Apache OpenSSL");
+    }
+}
+
 bench_header!(bench, Server, { vec![b"Some String".to_vec()] });
 
 standard_header!(Server, SERVER);
@@ -107,6 +107,8 @@ impl Header for SetCookie {
     }
 }
 
+__hyper__from_str!(SetCookie);
+
 #[test]
 fn test_set_cookie_fmt() {
     use ::header::Headers;
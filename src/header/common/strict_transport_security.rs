@@ -1,5 +1,6 @@
 use std::fmt;
 use std::str::{self, FromStr};
+use std::time::Duration;
 
 use unicase;
 
@@ -30,6 +31,7 @@ use header::{Header, RawLike, parsing};
 ///
 /// * `max-age=31536000`
 /// * `max-age=15768000 ; includeSubDomains`
+/// * `max-age=31536000; includeSubDomains; preload`
 ///
 /// # Example
 ///
@@ -54,7 +56,11 @@ pub struct StrictTransportSecurity {
     /// Specifies the number of seconds, after the reception of the STS header
     /// field, during which the UA regards the host (from whom the message was
     /// received) as a Known HSTS Host.
-    pub max_age: u64
+    pub max_age: u64,
+
+    /// Signals consent to inclusion of the host in browser HSTS preload
+    /// lists, via the non-standard `preload` directive.
+    pub preload: bool
 }
 
 impl StrictTransportSecurity {
@@ -62,7 +68,8 @@ impl StrictTransportSecurity {
     pub fn including_subdomains(max_age: u64) -> StrictTransportSecurity {
         StrictTransportSecurity {
             max_age: max_age,
-            include_subdomains: true
+            include_subdomains: true,
+            preload: false
         }
     }
 
@@ -70,14 +77,62 @@ impl StrictTransportSecurity {
     pub fn excluding_subdomains(max_age: u64) -> StrictTransportSecurity {
         StrictTransportSecurity {
             max_age: max_age,
-            include_subdomains: false
+            include_subdomains: false,
+            preload: false
+        }
+    }
+
+    /// Combine two policies into the more protective of the two: the longer
+    /// `max-age`, with `includeSubDomains` and `preload` if either has them.
+    ///
+    /// Useful when layering a default policy with a per-host override.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::StrictTransportSecurity;
+    ///
+    /// let default = StrictTransportSecurity::including_subdomains(86400);
+    /// let host = StrictTransportSecurity::excluding_subdomains(31536000);
+    /// assert_eq!(
+    ///     StrictTransportSecurity::stronger_of(&default, &host),
+    ///     StrictTransportSecurity::including_subdomains(31536000));
+    /// ```
+    pub fn stronger_of(a: &StrictTransportSecurity, b: &StrictTransportSecurity)
+        -> StrictTransportSecurity
+    {
+        StrictTransportSecurity {
+            max_age: ::std::cmp::max(a.max_age, b.max_age),
+            include_subdomains: a.include_subdomains || b.include_subdomains,
+            preload: a.preload || b.preload
         }
     }
+
+    /// Return true if this policy meets the requirements for submission to
+    /// the [HSTS preload list](https://hstspreload.org/) used by browsers:
+    /// a `max-age` of at least one year (31536000 seconds),
+    /// `includeSubDomains`, and the `preload` directive.
+    pub fn is_preload_eligible(&self) -> bool {
+        self.max_age >= PRELOAD_MIN_MAX_AGE && self.include_subdomains && self.preload
+    }
 }
 
+impl From<Duration> for StrictTransportSecurity {
+    /// An STS header that excludes subdomains, with the `max-age` converted
+    /// by `duration_to_secs_saturating`.
+    fn from(max_age: Duration) -> StrictTransportSecurity {
+        StrictTransportSecurity::excluding_subdomains(
+            u64::from(parsing::duration_to_secs_saturating(max_age)))
+    }
+}
+
+// One year, the minimum max-age for preload list submission.
+const PRELOAD_MIN_MAX_AGE: u64 = 31536000;
+
 enum Directive {
     MaxAge(u64),
     IncludeSubdomains,
+    Preload,
     Unknown
 }
 
@@ -89,6 +144,8 @@ impl FromStr for StrictTransportSecurity {
             .map(str::trim)
             .map(|sub| if unicase::eq_ascii(sub, "includeSubdomains") {
                 Ok(Directive::IncludeSubdomains)
+            } else if unicase::eq_ascii(sub, "preload") {
+                Ok(Directive::Preload)
             } else {
                 let mut sub = sub.splitn(2, '=');
                 match (sub.next(), sub.next()) {
@@ -103,18 +160,21 @@ impl FromStr for StrictTransportSecurity {
                     _ => Ok(Directive::Unknown)
                 }
             })
-            .fold(Ok((None, None)), |res, dir| match (res, dir) {
-                (Ok((None, sub)), Ok(Directive::MaxAge(age))) => Ok((Some(age), sub)),
-                (Ok((age, None)), Ok(Directive::IncludeSubdomains)) => Ok((age, Some(()))),
-                (Ok((Some(_), _)), Ok(Directive::MaxAge(_))) |
-                (Ok((_, Some(_))), Ok(Directive::IncludeSubdomains)) |
+            .fold(Ok((None, None, None)), |res, dir| match (res, dir) {
+                (Ok((None, sub, pre)), Ok(Directive::MaxAge(age))) => Ok((Some(age), sub, pre)),
+                (Ok((age, None, pre)), Ok(Directive::IncludeSubdomains)) => Ok((age, Some(()), pre)),
+                (Ok((age, sub, None)), Ok(Directive::Preload)) => Ok((age, sub, Some(()))),
+                (Ok((Some(_), _, _)), Ok(Directive::MaxAge(_))) |
+                (Ok((_, Some(_), _)), Ok(Directive::IncludeSubdomains)) |
+                (Ok((_, _, Some(_))), Ok(Directive::Preload)) |
                 (_, Err(_)) => Err(::Error::Header),
                 (res, _) => res
             })
             .and_then(|res| match res {
-                (Some(age), sub) => Ok(StrictTransportSecurity {
+                (Some(age), sub, pre) => Ok(StrictTransportSecurity {
                     max_age: age,
-                    include_subdomains: sub.is_some()
+                    include_subdomains: sub.is_some(),
+                    preload: pre.is_some()
                 }),
                 _ => Err(::Error::Header)
             })
@@ -140,11 +200,14 @@ impl Header for StrictTransportSecurity {
 
 impl fmt::Display for StrictTransportSecurity {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "max-age={}", self.max_age));
         if self.include_subdomains {
-            write!(f, "max-age={}; includeSubdomains", self.max_age)
-        } else {
-            write!(f, "max-age={}", self.max_age)
+            try!(f.write_str("; includeSubdomains"));
+        }
+        if self.preload {
+            try!(f.write_str("; preload"));
         }
+        Ok(())
     }
 }
 
@@ -152,12 +215,21 @@ impl fmt::Display for StrictTransportSecurity {
 mod tests {
     use super::StrictTransportSecurity;
     use header::{Header, Raw};
+    use test_support::assert_roundtrip;
+
+    #[test]
+    fn test_roundtrip() {
+        assert_roundtrip::<StrictTransportSecurity>(b"max-age=31536000");
+        assert_roundtrip::<StrictTransportSecurity>(b"max-age = \"0\"");
+        assert_roundtrip::<StrictTransportSecurity>(b"includeSubDomains; max-age=15768000");
+        assert_roundtrip::<StrictTransportSecurity>(b"max-age=31536000; preload; includesubdomains");
+    }
 
     #[test]
     fn test_parse_max_age() {
         let r: Raw = "max-age=31536000".into();
         let h = Header::parse_header(&r);
-        assert_eq!(h.ok(), Some(StrictTransportSecurity { include_subdomains: false, max_age: 31536000u64 }));
+        assert_eq!(h.ok(), Some(StrictTransportSecurity { include_subdomains: false, max_age: 31536000u64, preload: false }));
     }
 
     #[test]
@@ -171,21 +243,21 @@ mod tests {
     fn test_parse_quoted_max_age() {
         let r: Raw = "max-age=\"31536000\"".into();
         let h = Header::parse_header(&r);
-        assert_eq!(h.ok(), Some(StrictTransportSecurity { include_subdomains: false, max_age: 31536000u64 }));
+        assert_eq!(h.ok(), Some(StrictTransportSecurity { include_subdomains: false, max_age: 31536000u64, preload: false }));
     }
 
     #[test]
     fn test_parse_spaces_max_age() {
         let r: Raw = "max-age = 31536000".into();
         let h = Header::parse_header(&r);
-        assert_eq!(h.ok(), Some(StrictTransportSecurity { include_subdomains: false, max_age: 31536000u64 }));
+        assert_eq!(h.ok(), Some(StrictTransportSecurity { include_subdomains: false, max_age: 31536000u64, preload: false }));
     }
 
     #[test]
     fn test_parse_include_subdomains() {
         let r: Raw = "max-age=15768000 ; includeSubDomains".into();
         let h = Header::parse_header(&r);
-        assert_eq!(h.ok(), Some(StrictTransportSecurity { include_subdomains: true, max_age: 15768000u64 }));
+        assert_eq!(h.ok(), Some(StrictTransportSecurity { include_subdomains: true, max_age: 15768000u64, preload: false }));
     }
 
     #[test]
@@ -207,6 +279,70 @@ mod tests {
         let r: Raw = "max-age=100; max-age=5; max-age=0".into();
         assert!(StrictTransportSecurity::parse_header(&r).is_err());
     }
+
+    #[test]
+    fn test_parse_preload() {
+        let r: Raw = "max-age=31536000; includeSubDomains; preload".into();
+        let h: ::Result<StrictTransportSecurity> = Header::parse_header(&r);
+        assert_eq!(h.ok(), Some(StrictTransportSecurity { include_subdomains: true, max_age: 31536000u64, preload: true }));
+        assert_roundtrip::<StrictTransportSecurity>(b"max-age=31536000; includeSubdomains; preload");
+    }
+
+    #[test]
+    fn test_parse_duplicate_preload() {
+        let r: Raw = "max-age=31536000; preload; Preload".into();
+        assert!(StrictTransportSecurity::parse_header(&r).is_err());
+    }
+
+    #[test]
+    fn test_stronger_of() {
+        let short = StrictTransportSecurity::including_subdomains(300);
+        let long = StrictTransportSecurity::excluding_subdomains(63072000);
+        let expected = StrictTransportSecurity::including_subdomains(63072000);
+        assert_eq!(StrictTransportSecurity::stronger_of(&short, &long), expected);
+        assert_eq!(StrictTransportSecurity::stronger_of(&long, &short), expected);
+
+        let preload = StrictTransportSecurity { max_age: 0, include_subdomains: false, preload: true };
+        let strongest = StrictTransportSecurity::stronger_of(&expected, &preload);
+        assert_eq!(strongest, StrictTransportSecurity {
+            max_age: 63072000,
+            include_subdomains: true,
+            preload: true
+        });
+        assert!(strongest.is_preload_eligible());
+        assert_eq!(StrictTransportSecurity::stronger_of(&long, &long), long);
+    }
+
+    #[test]
+    fn test_preload_eligible() {
+        let r: Raw = "max-age=63072000; includeSubDomains; preload".into();
+        let h = StrictTransportSecurity::parse_header(&r).unwrap();
+        assert!(h.is_preload_eligible());
+
+        let h = StrictTransportSecurity {
+            max_age: 31536000,
+            include_subdomains: true,
+            preload: true
+        };
+        assert!(h.is_preload_eligible());
+    }
+
+    #[test]
+    fn test_preload_ineligible() {
+        // max-age less than one year
+        let r: Raw = "max-age=31535999; includeSubDomains; preload".into();
+        let h = StrictTransportSecurity::parse_header(&r).unwrap();
+        assert!(!h.is_preload_eligible());
+
+        // missing includeSubDomains
+        let r: Raw = "max-age=63072000; preload".into();
+        let h = StrictTransportSecurity::parse_header(&r).unwrap();
+        assert!(!h.is_preload_eligible());
+
+        // missing preload
+        let h = StrictTransportSecurity::including_subdomains(63072000);
+        assert!(!h.is_preload_eligible());
+    }
 }
 
 bench_header!(bench, StrictTransportSecurity, { vec![b"max-age=15768000 ; includeSubDomains".to_vec()] });
@@ -60,6 +60,10 @@ header! {
     ///     ])
     /// );
     /// ```
+    ///
+    /// Codings which are not valid transfer-codings, other than `trailers`,
+    /// are still parsed, keeping the other preferences. Check them with
+    /// `is_valid`.
     (Te, "TE") => (QualityItem<Encoding>)*
 
     test_te {
@@ -67,6 +71,32 @@ header! {
         test_header!(test1, vec![b"trailers"]);
         test_header!(test2, vec![b"trailers, deflate;q=0.5"]);
         test_header!(test3, vec![b""]);
+
+        #[test]
+        fn test_is_valid() {
+            let r: Raw = "Trailers, deflate;q=0.5".into();
+            assert!(HeaderField::parse_header(&r).unwrap().is_valid());
+            let r: Raw = "trailers, br".into();
+            let te = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(te.len(), 2);
+            assert!(!te.is_valid());
+            let r: Raw = "Trailers, BR".into();
+            assert!(!HeaderField::parse_header(&r).unwrap().is_valid());
+        }
+    }
+}
+
+__hyper__list!(Te => QualityItem<Encoding>);
+__hyper__from_str!(Te);
+
+impl Te {
+    /// Check that every coding is `trailers` or a valid transfer-coding.
+    pub fn is_valid(&self) -> bool {
+        self.iter().all(|item| match item.item {
+            Encoding::Trailers => true,
+            Encoding::EncodingExt(ref s) if s.eq_ignore_ascii_case("trailers") => true,
+            ref coding => coding.is_valid_transfer_coding(),
+        })
     }
 }
 
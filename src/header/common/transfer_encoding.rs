@@ -39,6 +39,10 @@ header! {
     ///     ])
     /// );
     /// ```
+    ///
+    /// Codings which are not valid transfer-codings, such as `br`, are still
+    /// parsed, so that the framing of the message remains visible. Check
+    /// them with `is_valid`.
     (TransferEncoding, "Transfer-Encoding") => (Encoding)+
 
     transfer_encoding {
@@ -55,15 +59,46 @@ header! {
             Some(HeaderField(
                 vec![Encoding::Chunked, Encoding::Chunked]
             )));
+        test_header!(test_chunked, vec![b"chunked"], Some(TransferEncoding::chunked()));
+        test_header!(test_empty, vec![b""], Some(TransferEncoding(vec![])));
+
+        #[test]
+        fn test_is_valid() {
+            let r: Raw = "gzip, chunked".into();
+            assert!(HeaderField::parse_header(&r).unwrap().is_valid());
+            let r: Raw = "br, chunked".into();
+            let te = HeaderField::parse_header(&r).unwrap();
+            assert_eq!(te, HeaderField(vec![Encoding::Brotli, Encoding::Chunked]));
+            assert!(!te.is_valid());
+            let r: Raw = "IDENTITY".into();
+            assert!(!HeaderField::parse_header(&r).unwrap().is_valid());
+        }
 
     }
 }
 
+__hyper__list!(TransferEncoding => Encoding);
+__hyper__from_str!(TransferEncoding);
+
 impl TransferEncoding {
     /// Constructor for the most common Transfer-Encoding, `chunked`.
     pub fn chunked() -> TransferEncoding {
         TransferEncoding(vec![Encoding::Chunked])
     }
+
+    /// Check that every coding is a valid transfer-coding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{TransferEncoding, Encoding};
+    ///
+    /// assert!(TransferEncoding(vec![Encoding::Gzip, Encoding::Chunked]).is_valid());
+    /// assert!(!TransferEncoding(vec![Encoding::Brotli, Encoding::Chunked]).is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        self.iter().all(Encoding::is_valid_transfer_coding)
+    }
 }
 
 bench_header!(normal, TransferEncoding, { vec![b"chunked, gzip".to_vec()] });
@@ -1,3 +1,4 @@
+use std::error::Error as StdError;
 use std::fmt::{self, Display};
 use std::str::FromStr;
 use unicase;
@@ -79,9 +80,35 @@ header! {
     }
 }
 
+__hyper__list!(Upgrade => Protocol);
+__hyper__from_str!(Upgrade);
+
+/// An inconsistency between the `Upgrade` and `Connection` headers, as
+/// reported by `Headers::check_upgrade`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpgradeMismatch {
+    /// `Upgrade` is present, but `Connection` does not list `upgrade`.
+    MissingConnectionUpgrade,
+    /// `Connection` lists `upgrade`, but `Upgrade` is absent.
+    MissingUpgrade,
+}
+
+impl Display for UpgradeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            UpgradeMismatch::MissingConnectionUpgrade =>
+                "Upgrade header present without the Connection: upgrade option",
+            UpgradeMismatch::MissingUpgrade =>
+                "Connection: upgrade option present without an Upgrade header",
+        })
+    }
+}
+
+impl StdError for UpgradeMismatch {}
+
 /// A protocol name used to identify a specific protocol. Names are case-sensitive
 /// except for the `WebSocket` value.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum ProtocolName {
     /// `HTTP` value, Hypertext Transfer Protocol
     Http,
@@ -127,7 +154,7 @@ impl Display for ProtocolName {
 }
 
 /// Protocols that appear in the `Upgrade` header field
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Protocol {
     /// The protocol identifier
     pub name: ProtocolName,
@@ -1,3 +1,5 @@
+use header::Server;
+
 header! {
     /// `User-Agent` header, defined in
     /// [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.5.3)
@@ -45,4 +47,38 @@ header! {
     }
 }
 
+__hyper__from_str!(UserAgent);
+
+impl UserAgent {
+    /// Return a copy of this `UserAgent` with the version of every product
+    /// removed, leaving product names and comments intact.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::UserAgent;
+    ///
+    /// let ua = UserAgent::new("CERN-LineMode/2.15 libwww/2.17b3");
+    /// assert_eq!(&*ua.redact_versions(), "CERN-LineMode libwww");
+    /// ```
+    pub fn redact_versions(&self) -> UserAgent {
+        UserAgent::new(Server::new(self.to_string()).redact_versions().to_string())
+    }
+
+    /// Return a copy of this `UserAgent` with all comments removed, including
+    /// nested ones, leaving the products.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::UserAgent;
+    ///
+    /// let ua = UserAgent::new("Mozilla/5.0 (X11; Linux x86_64) Gecko/20100101");
+    /// assert_eq!(&*ua.strip_comments(), "Mozilla/5.0 Gecko/20100101");
+    /// ```
+    pub fn strip_comments(&self) -> UserAgent {
+        UserAgent::new(Server::new(self.to_string()).strip_comments().to_string())
+    }
+}
+
 standard_header!(UserAgent, USER_AGENT);
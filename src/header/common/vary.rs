@@ -74,4 +74,6 @@ header! {
     }
 }
 
+__hyper__from_str!(Vary);
+
 standard_header!(Vary, VARY);
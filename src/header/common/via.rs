@@ -0,0 +1,271 @@
+use std::fmt;
+use std::str::{self, FromStr};
+
+use header::{Header, RawLike};
+use header::parsing::fmt_comma_delimited;
+
+/// `Via` header, defined in
+/// [RFC7230](https://tools.ietf.org/html/rfc7230#section-5.7.1)
+///
+/// The `Via` header field indicates the presence of intermediate
+/// protocols and recipients between the user agent and the server on
+/// requests, and between the origin server and the client on responses.
+/// Each intermediary appends an entry for itself.
+///
+/// Entries are split on commas outside of comments, and a malformed entry
+/// rejects the whole header, rather than losing track of an intermediary.
+///
+/// # ABNF
+///
+/// ```text
+/// Via = 1#( received-protocol RWS received-by [ RWS comment ] )
+///
+/// received-protocol = [ protocol-name "/" ] protocol-version
+/// received-by       = ( uri-host [ ":" port ] ) / pseudonym
+/// pseudonym         = token
+/// ```
+///
+/// # Example values
+///
+/// * `1.0 fred, 1.1 p.example.net`
+/// * `HTTP/1.1 GWA`
+/// * `1.1 proxy.example.com (Apache/2.4)`
+///
+/// # Examples
+///
+/// ```
+/// use hyperx::header::{Headers, Via, ViaEntry};
+///
+/// let mut headers = Headers::new();
+/// headers.set(Via(vec![
+///     ViaEntry::new("1.0", "fred"),
+///     ViaEntry::new("1.1", "p.example.net"),
+/// ]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Via(pub Vec<ViaEntry>);
+
+__hyper__deref!(Via => Vec<ViaEntry>);
+__hyper__list!(Via => ViaEntry);
+
+impl Header for Via {
+    fn header_name() -> &'static str {
+        static NAME: &'static str = "Via";
+        NAME
+    }
+
+    fn parse_header<'a, T>(raw: &'a T) -> ::Result<Via>
+    where T: RawLike<'a>
+    {
+        let mut entries = Vec::new();
+        for line in raw.iter() {
+            for entry in try!(split_entries(try!(str::from_utf8(line)))) {
+                let entry = entry.trim();
+                if !entry.is_empty() {
+                    entries.push(try!(entry.parse()));
+                }
+            }
+        }
+        if entries.is_empty() {
+            Err(::Error::Header)
+        } else {
+            Ok(Via(entries))
+        }
+    }
+
+    fn fmt_header(&self, f: &mut ::header::Formatter) -> fmt::Result {
+        f.fmt_line(self)
+    }
+}
+
+__hyper__from_str!(Via);
+
+impl fmt::Display for Via {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_comma_delimited(f, &self[..])
+    }
+}
+
+// Split on commas outside of comments, which may be nested and contain
+// quoted-pairs.
+fn split_entries(line: &str) -> ::Result<Vec<&str>> {
+    let mut entries = Vec::new();
+    let mut depth = 0;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, b) in line.bytes().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match b {
+            b'\\' if depth > 0 => escaped = true,
+            b'(' => depth += 1,
+            b')' if depth == 0 => return Err(::Error::Header),
+            b')' => depth -= 1,
+            b',' if depth == 0 => {
+                entries.push(&line[start..i]);
+                start = i + 1;
+            },
+            _ => (),
+        }
+    }
+    if depth > 0 {
+        return Err(::Error::Header);
+    }
+    entries.push(&line[start..]);
+    Ok(entries)
+}
+
+/// An entry of the `Via` header, for a single intermediary.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct ViaEntry {
+    /// The name of the received protocol, if other than HTTP.
+    pub protocol_name: Option<String>,
+    /// The version of the received protocol.
+    pub protocol_version: String,
+    /// The host, and optional port, or a pseudonym of the intermediary.
+    pub received_by: String,
+    /// An optional comment, such as the software of the intermediary,
+    /// without the enclosing parentheses.
+    pub comment: Option<String>,
+}
+
+impl ViaEntry {
+    /// Create a new entry for an HTTP intermediary, without a comment.
+    pub fn new<V, R>(protocol_version: V, received_by: R) -> ViaEntry
+    where V: Into<String>, R: Into<String>
+    {
+        ViaEntry {
+            protocol_name: None,
+            protocol_version: protocol_version.into(),
+            received_by: received_by.into(),
+            comment: None,
+        }
+    }
+}
+
+impl FromStr for ViaEntry {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> ::Result<ViaEntry> {
+        let mut parts = s.trim().splitn(2, &[' ', '\t'][..]);
+        let protocol = parts.next().unwrap_or("");
+        let rest = parts.next().unwrap_or("").trim();
+        let mut parts = rest.splitn(2, &[' ', '\t'][..]);
+        let received_by = parts.next().unwrap_or("");
+        let comment = parts.next().map(str::trim);
+        if protocol.is_empty() || received_by.is_empty() {
+            return Err(::Error::Header);
+        }
+
+        let (protocol_name, protocol_version) = match protocol.find('/') {
+            Some(idx) => (Some(&protocol[..idx]), &protocol[idx + 1..]),
+            None => (None, protocol),
+        };
+        if protocol_name == Some("") || protocol_version.is_empty() {
+            return Err(::Error::Header);
+        }
+
+        let comment = match comment {
+            Some(c) if c.len() >= 2 && c.starts_with('(') && c.ends_with(')') => {
+                Some(c[1..c.len() - 1].to_owned())
+            },
+            Some(_) => return Err(::Error::Header),
+            None => None,
+        };
+
+        Ok(ViaEntry {
+            protocol_name: protocol_name.map(ToOwned::to_owned),
+            protocol_version: protocol_version.to_owned(),
+            received_by: received_by.to_owned(),
+            comment: comment,
+        })
+    }
+}
+
+impl fmt::Display for ViaEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref name) = self.protocol_name {
+            try!(write!(f, "{}/", name));
+        }
+        try!(write!(f, "{} {}", self.protocol_version, self.received_by));
+        if let Some(ref comment) = self.comment {
+            try!(write!(f, " ({})", comment));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Via, ViaEntry};
+    use header::{Header, Raw};
+    use test_support::assert_roundtrip;
+
+    #[test]
+    fn test_parse() {
+        // From the RFC
+        let r: Raw = "1.0 fred, 1.1 p.example.net".into();
+        assert_eq!(Via::parse_header(&r).unwrap(), Via(vec![
+            ViaEntry::new("1.0", "fred"),
+            ViaEntry::new("1.1", "p.example.net"),
+        ]));
+
+        let r: Raw = "HTTP/1.1 GWA".into();
+        assert_eq!(Via::parse_header(&r).unwrap(), Via(vec![ViaEntry {
+            protocol_name: Some("HTTP".to_owned()),
+            protocol_version: "1.1".to_owned(),
+            received_by: "GWA".to_owned(),
+            comment: None,
+        }]));
+
+        let r: Raw = "1.1 proxy.example.com:8080 (Apache/2.4)".into();
+        assert_eq!(Via::parse_header(&r).unwrap(), Via(vec![ViaEntry {
+            protocol_name: None,
+            protocol_version: "1.1".to_owned(),
+            received_by: "proxy.example.com:8080".to_owned(),
+            comment: Some("Apache/2.4".to_owned()),
+        }]));
+
+        assert_roundtrip::<Via>(b"1.0 fred, 1.1 p.example.net");
+        assert_roundtrip::<Via>(b"HTTP/1.1 GWA (a (nested, comment) \\) b)");
+    }
+
+    #[test]
+    fn test_comment_with_comma() {
+        let r: Raw = vec![b"1.1 fred (Apache, mod_proxy), 1.1 bar".to_vec(), b"1.0 baz".to_vec()]
+            .into();
+        let via = Via::parse_header(&r).unwrap();
+        assert_eq!(via, Via(vec![
+            ViaEntry {
+                comment: Some("Apache, mod_proxy".to_owned()),
+                ..ViaEntry::new("1.1", "fred")
+            },
+            ViaEntry::new("1.1", "bar"),
+            ViaEntry::new("1.0", "baz"),
+        ]));
+        assert_eq!(via.to_string(), "1.1 fred (Apache, mod_proxy), 1.1 bar, 1.0 baz");
+    }
+
+    #[test]
+    fn test_invalid() {
+        let invalid = [
+            "",
+            "1.1",
+            "1.1 fred not-a-comment",
+            "1.1 fred, 1.1",
+            "1.1 fred (Apache, mod_proxy",
+            "1.1 fred (Apache)), 1.1 bar",
+        ];
+        for v in invalid.iter() {
+            let r: Raw = (*v).into();
+            assert!(Via::parse_header(&r).is_err(), "{} should be invalid", v);
+        }
+    }
+}
+
+bench_header!(bench, Via, { vec![b"1.0 fred, 1.1 p.example.net".to_vec()] });
+bench_list_header!(bench_list, Via, b"1.1 p.example.net (Apache/1.1)", 16);
+
+standard_header!(Via, VIA);
@@ -0,0 +1,87 @@
+use std::fmt;
+use header::{Header, QualityItem, RawLike};
+use header::parsing::{from_comma_delimited, fmt_comma_delimited};
+
+/// `Want-Digest` header, defined in
+/// [RFC3230](https://tools.ietf.org/html/rfc3230#section-4.3.1)
+///
+/// The `Want-Digest` header field indicates the digest algorithms, in order
+/// of preference, that the sender wants a digest of the instance computed
+/// with.
+///
+/// # ABNF
+///
+/// ```text
+/// Want-Digest = "Want-Digest" ":" #(digest-algorithm [ ";" "q" "=" qvalue])
+/// ```
+///
+/// # Example values
+/// * `MD5`
+/// * `SHA, MD5;q=0.5`
+/// * `sha-256;q=1, sha;q=0.1`
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{Headers, WantDigest, QualityItem, q, qitem};
+///
+/// let mut headers = Headers::new();
+/// headers.set(WantDigest(vec![
+///     qitem("sha-256".to_owned()),
+///     QualityItem::new("md5".to_owned(), q(300)),
+/// ]));
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct WantDigest(pub Vec<QualityItem<String>>);
+
+__hyper__deref!(WantDigest => Vec<QualityItem<String>>);
+
+impl Header for WantDigest {
+    fn header_name() -> &'static str {
+        static NAME: &'static str = "Want-Digest";
+        NAME
+    }
+
+    fn parse_header<'a, T>(raw: &'a T) -> ::Result<WantDigest>
+    where T: RawLike<'a>
+    {
+        let algorithms = try!(from_comma_delimited(raw));
+        if !algorithms.is_empty() {
+            Ok(WantDigest(algorithms))
+        } else {
+            Err(::Error::Header)
+        }
+    }
+
+    fn fmt_header(&self, f: &mut ::header::Formatter) -> fmt::Result {
+        f.fmt_line(self)
+    }
+}
+
+__hyper__from_str!(WantDigest);
+
+impl fmt::Display for WantDigest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_comma_delimited(f, &self[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WantDigest;
+    use header::{Header, QualityItem, Raw, q, qitem};
+
+    #[test]
+    fn test_parse() {
+        let r: Raw = "SHA-256, md5;q=0.3".into();
+        let a: WantDigest = Header::parse_header(&r).unwrap();
+        assert_eq!(a, WantDigest(vec![
+            qitem("SHA-256".to_owned()),
+            QualityItem::new("md5".to_owned(), q(300)),
+        ]));
+        assert_eq!(a.to_string(), "SHA-256, md5; q=0.3");
+
+        let r: Raw = "".into();
+        assert!(WantDigest::parse_header(&r).is_err());
+    }
+}
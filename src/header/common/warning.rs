@@ -1,7 +1,7 @@
 use std::fmt;
 use std::str::{FromStr};
-use header::{Header, HttpDate, RawLike};
-use header::parsing::from_one_raw_str;
+use header::{Header, Headers, HttpDate, RawLike};
+use header::parsing::{comma_delimited_iter, from_one_raw_str, from_raw_str};
 
 /// `Warning` header, defined in [RFC7234](https://tools.ietf.org/html/rfc7234#section-5.5)
 ///
@@ -88,6 +88,52 @@ pub struct Warning {
     pub date: Option<HttpDate>
 }
 
+impl Warning {
+    /// Remove the `1xx` warnings from the `Warning` headers of a stored
+    /// response, keeping any `2xx` warnings.
+    ///
+    /// As defined in
+    /// [RFC7234](https://tools.ietf.org/html/rfc7234#section-4.3.4), a cache
+    /// must delete `1xx` warnings, which describe the freshness or
+    /// revalidation of the response, after successfully revalidating it.
+    /// Each remaining `warning-value` is kept as its own field line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, Warning};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.append_raw("Warning", "110 - \"Response is Stale\"");
+    /// Warning::strip_on_revalidation(&mut headers);
+    /// assert!(!headers.has::<Warning>());
+    /// ```
+    pub fn strip_on_revalidation(headers: &mut Headers) {
+        let mut kept = ::header::raw::new();
+        match headers.get_raw(Warning::header_name()) {
+            Some(raw) => {
+                for value in comma_delimited_iter(raw).filter_map(|value| value.ok()) {
+                    if !is_1xx(value) {
+                        kept.push(value.as_bytes().to_vec());
+                    }
+                }
+            },
+            None => return,
+        }
+        if kept.len() == 0 {
+            headers.remove::<Warning>();
+        } else {
+            headers.set_raw(Warning::header_name(), kept);
+        }
+    }
+}
+
+// Whether a `warning-value` has a `1xx` warn-code.
+fn is_1xx(value: &str) -> bool {
+    let code = value.split(' ').next().unwrap_or("");
+    code.len() == 3 && code.starts_with('1') && code.bytes().all(|b| b.is_ascii_digit())
+}
+
 impl Header for Warning {
     fn header_name() -> &'static str {
         static NAME: &'static str = "Warning";
@@ -100,6 +146,23 @@ impl Header for Warning {
         from_one_raw_str(raw)
     }
 
+    /// Parse a single `warning-value`, returning any bytes that follow it
+    /// (after the `warn-text` and optional `warn-date`) as the remainder.
+    fn parse_header_partial<'a, T>(raw: &'a T) -> ::Result<(Warning, &'a [u8])>
+    where T: RawLike<'a>
+    {
+        let line = match raw.one() {
+            Some(line) => line,
+            None => return Err(::Error::Header)
+        };
+        let end = match warning_value_len(line) {
+            Some(end) => end,
+            None => return Err(::Error::Header)
+        };
+        let warning = try!(from_raw_str(&line[..end]));
+        Ok((warning, &line[end..]))
+    }
+
     fn fmt_header(&self, f: &mut ::header::Formatter) -> fmt::Result {
         f.fmt_line(self)
     }
@@ -150,10 +213,38 @@ impl FromStr for Warning {
     }
 }
 
+// Return the length of the leading `warning-value` in `line`, ending after
+// the quoted `warn-text`, or the quoted `warn-date` if one follows.
+fn warning_value_len(line: &[u8]) -> Option<usize> {
+    let quoted_end = |start: usize| {
+        line[start + 1..].iter().position(|&b| b == b'"').map(|i| start + i + 2)
+    };
+    let text_start = match line.iter().position(|&b| b == b'"') {
+        Some(i) => i,
+        None => return None
+    };
+    let text_end = match quoted_end(text_start) {
+        Some(i) => i,
+        None => return None
+    };
+    let date_start = text_end + line[text_end..].iter()
+        .take_while(|&&b| b == b' ')
+        .count();
+    if date_start > text_end && line.get(date_start) == Some(&b'"') {
+        if let Some(date_end) = quoted_end(date_start) {
+            let date = ::std::str::from_utf8(&line[date_start + 1..date_end - 1]);
+            if date.ok().and_then(|d| d.parse::<HttpDate>().ok()).is_some() {
+                return Some(date_end);
+            }
+        }
+    }
+    Some(text_end)
+}
+
 #[cfg(test)]
 mod tests {
     use super::Warning;
-    use header::{Header, HttpDate, Raw};
+    use header::{Header, Headers, HttpDate, Raw};
 
     #[test]
     fn test_parsing() {
@@ -193,6 +284,68 @@ mod tests {
             date: "Tue, 15 Nov 1994 08:12:31 GMT".parse::<HttpDate>().ok()
         }));
     }
+
+    #[test]
+    fn test_strip_on_revalidation() {
+        let mut headers = Headers::new();
+        headers.append_raw("Warning", "110 - \"Response is Stale\"");
+        headers.append_raw("Warning", "214 proxy.example \"Transformation Applied\" \
+                                       \"Sat, 25 Aug 2012 23:34:45 GMT\", \
+                                       111 - \"Revalidation Failed\"");
+        Warning::strip_on_revalidation(&mut headers);
+        assert_eq!(headers.get_raw("Warning").unwrap(),
+                   "214 proxy.example \"Transformation Applied\" \"Sat, 25 Aug 2012 23:34:45 GMT\"");
+        assert_eq!(headers.get::<Warning>().unwrap().code, 214);
+
+        Warning::strip_on_revalidation(&mut headers);
+        assert_eq!(headers.get::<Warning>().unwrap().code, 214);
+
+        let mut headers = Headers::new();
+        headers.set(Warning {
+            code: 110,
+            agent: "-".to_owned(),
+            text: "Response is Stale".to_owned(),
+            date: None
+        });
+        Warning::strip_on_revalidation(&mut headers);
+        assert!(!headers.has::<Warning>());
+
+        let mut headers = Headers::new();
+        Warning::strip_on_revalidation(&mut headers);
+        assert!(!headers.has::<Warning>());
+    }
+
+    #[test]
+    fn test_parsing_partial() {
+        let r: Raw = vec![
+            b"112 - \"network down\" \"Sat, 25 Aug 2012 23:34:45 GMT\" \
+              (from cache)".to_vec()
+        ].into();
+        let (warning, rest) = Warning::parse_header_partial(&r).unwrap();
+        assert_eq!(warning, Warning {
+            code: 112,
+            agent: "-".to_owned(),
+            text: "network down".to_owned(),
+            date: "Sat, 25 Aug 2012 23:34:45 GMT".parse::<HttpDate>().ok()
+        });
+        assert_eq!(rest, b" (from cache)");
+
+        let r: Raw = vec![
+            b"299 - \"Deprecated\" \"not a date\"".to_vec()
+        ].into();
+        let (warning, rest) = Warning::parse_header_partial(&r).unwrap();
+        assert_eq!(warning.text, "Deprecated");
+        assert_eq!(warning.date, None);
+        assert_eq!(rest, b" \"not a date\"");
+
+        let r: Raw = vec![b"299 - \"Deprecated\"".to_vec()].into();
+        let (warning, rest) = Warning::parse_header_partial(&r).unwrap();
+        assert_eq!(warning.text, "Deprecated");
+        assert_eq!(rest, b"");
+
+        let r: Raw = vec![b"299 - \"Deprecated".to_vec()].into();
+        assert!(Warning::parse_header_partial(&r).is_err());
+    }
 }
 
 standard_header!(Warning, WARNING);
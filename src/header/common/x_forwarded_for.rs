@@ -0,0 +1,112 @@
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+use unicase;
+
+header! {
+    /// `X-Forwarded-For` header, a de-facto standard
+    ///
+    /// The `X-Forwarded-For` header field lists the addresses of the client
+    /// and of each proxy a request passed through, with the original client
+    /// first. It is superseded by `Forwarded`, but still widely set by load
+    /// balancers and reverse proxies.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// X-Forwarded-For = 1#node
+    /// ```
+    ///
+    /// # Example values
+    /// * `203.0.113.195`
+    /// * `203.0.113.195, 70.41.3.18, 150.172.238.178`
+    /// * `2001:db8:85a3:8d3:1319:8a2e:370:7348, unknown`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, XForwardedFor, ForwardedNode};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(XForwardedFor(vec![
+    ///     ForwardedNode::Ip("203.0.113.195".parse().unwrap()),
+    ///     ForwardedNode::Unknown,
+    /// ]));
+    /// ```
+    (XForwardedFor, "X-Forwarded-For") => (ForwardedNode)+
+
+    test_x_forwarded_for {
+        test_header!(test_single, vec![b"203.0.113.195"]);
+        test_header!(
+            test_multiple,
+            vec![b"203.0.113.195, 2001:db8::1, 70.41.3.18"],
+            Some(HeaderField(vec![
+                ForwardedNode::Ip("203.0.113.195".parse().unwrap()),
+                ForwardedNode::Ip("2001:db8::1".parse().unwrap()),
+                ForwardedNode::Ip("70.41.3.18".parse().unwrap()),
+            ])));
+        test_header!(
+            test_fallback,
+            vec![b"unknown, _hidden, ::1"],
+            Some(HeaderField(vec![
+                ForwardedNode::Unknown,
+                ForwardedNode::Obfuscated("_hidden".to_owned()),
+                ForwardedNode::Ip("::1".parse().unwrap()),
+            ])));
+
+        #[test]
+        fn test_bracketed_ipv6() {
+            let r: Raw = "[2001:db8::1], _hidden".into();
+            let xff: HeaderField = Header::parse_header(&r).unwrap();
+            assert_eq!(xff.0[0], ForwardedNode::Ip("2001:db8::1".parse().unwrap()));
+            assert_eq!(xff.to_string(), "2001:db8::1, _hidden");
+        }
+    }
+}
+
+__hyper__list!(XForwardedFor => ForwardedNode);
+__hyper__from_str!(XForwardedFor);
+
+/// The address of a node in an `X-Forwarded-For` list.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ForwardedNode {
+    /// The IP address of the node.
+    Ip(IpAddr),
+    /// `unknown`, the address of the node is not known.
+    Unknown,
+    /// Any other identifier, such as an obfuscated `_hidden` node.
+    Obfuscated(String),
+}
+
+impl FromStr for ForwardedNode {
+    type Err = ::Error;
+    fn from_str(s: &str) -> ::Result<ForwardedNode> {
+        if s.is_empty() {
+            return Err(::Error::Header);
+        }
+        if unicase::eq_ascii(s, "unknown") {
+            return Ok(ForwardedNode::Unknown);
+        }
+        let ip = if s.starts_with('[') && s.ends_with(']') {
+            s[1..s.len() - 1].parse()
+        } else {
+            s.parse()
+        };
+        Ok(match ip {
+            Ok(ip) => ForwardedNode::Ip(ip),
+            Err(_) => ForwardedNode::Obfuscated(s.to_owned()),
+        })
+    }
+}
+
+impl fmt::Display for ForwardedNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ForwardedNode::Ip(ref ip) => fmt::Display::fmt(ip, f),
+            ForwardedNode::Unknown => f.write_str("unknown"),
+            ForwardedNode::Obfuscated(ref s) => f.write_str(s),
+        }
+    }
+}
+
+bench_header!(bench, XForwardedFor, { vec![b"203.0.113.195, 70.41.3.18".to_vec()] });
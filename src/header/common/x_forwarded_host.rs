@@ -0,0 +1,34 @@
+header! {
+    /// `X-Forwarded-Host` header, a de-facto standard
+    ///
+    /// The `X-Forwarded-Host` header field holds the `Host` requested by the
+    /// original client, which a reverse proxy may have changed when
+    /// forwarding the request. It is superseded by the `host` parameter of
+    /// `Forwarded`.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// X-Forwarded-Host = uri-host [ ":" port ]
+    /// ```
+    ///
+    /// # Example values
+    /// * `example.com`
+    /// * `example.com:8080`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::{Headers, XForwardedHost};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set(XForwardedHost::new("example.com"));
+    /// ```
+    (XForwardedHost, "X-Forwarded-Host") => Cow[str]
+
+    test_x_forwarded_host {
+        test_header!(test1, vec![b"example.com:8080"], Some(HeaderField::new("example.com:8080")));
+    }
+}
+
+__hyper__from_str!(XForwardedHost);
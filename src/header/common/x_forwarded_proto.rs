@@ -0,0 +1,101 @@
+use std::fmt;
+use unicase;
+
+use header::{Header, RawLike, parsing};
+
+/// `X-Forwarded-Proto` header, a de-facto standard
+///
+/// The `X-Forwarded-Proto` header field holds the protocol (`http` or
+/// `https`) the original client used to connect to a proxy or load
+/// balancer. It is superseded by the `proto` parameter of `Forwarded`.
+///
+/// If a list of protocols is received, as some chains of proxies produce,
+/// the first one (that of the original client) is used.
+///
+/// # ABNF
+///
+/// ```text
+/// X-Forwarded-Proto = uri-scheme
+/// ```
+///
+/// # Example values
+/// * `https`
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{Headers, XForwardedProto};
+///
+/// let mut headers = Headers::new();
+/// headers.set(XForwardedProto::Https);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum XForwardedProto {
+    /// `http`
+    Http,
+    /// `https`
+    Https,
+    /// Any other scheme, such as `ws`.
+    Ext(String),
+}
+
+impl Header for XForwardedProto {
+    fn header_name() -> &'static str {
+        static NAME: &'static str = "X-Forwarded-Proto";
+        NAME
+    }
+
+    fn parse_header<'a, T>(raw: &'a T) -> ::Result<XForwardedProto>
+    where T: RawLike<'a>
+    {
+        let protos: Vec<String> = try!(parsing::from_comma_delimited(raw));
+        match protos.into_iter().next() {
+            Some(ref proto) if unicase::eq_ascii(&**proto, "http") => Ok(XForwardedProto::Http),
+            Some(ref proto) if unicase::eq_ascii(&**proto, "https") => Ok(XForwardedProto::Https),
+            Some(proto) => Ok(XForwardedProto::Ext(proto)),
+            None => Err(::Error::Header),
+        }
+    }
+
+    fn fmt_header(&self, f: &mut ::header::Formatter) -> fmt::Result {
+        f.fmt_line(self)
+    }
+}
+
+__hyper__from_str!(XForwardedProto);
+
+impl fmt::Display for XForwardedProto {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            XForwardedProto::Http => "http",
+            XForwardedProto::Https => "https",
+            XForwardedProto::Ext(ref s) => s,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XForwardedProto;
+    use header::{Header, Raw};
+
+    #[test]
+    fn test_parse_header() {
+        let r: Raw = "https".into();
+        let a: XForwardedProto = Header::parse_header(&r).unwrap();
+        assert_eq!(a, XForwardedProto::Https);
+
+        let r: Raw = "HTTP".into();
+        let a: XForwardedProto = Header::parse_header(&r).unwrap();
+        assert_eq!(a, XForwardedProto::Http);
+
+        let r: Raw = "wss, https".into();
+        let a: XForwardedProto = Header::parse_header(&r).unwrap();
+        assert_eq!(a, XForwardedProto::Ext("wss".to_owned()));
+        assert_eq!(a.to_string(), "wss");
+
+        let r: Raw = "".into();
+        let e: ::Result<XForwardedProto> = Header::parse_header(&r);
+        assert!(e.is_err());
+    }
+}
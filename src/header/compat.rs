@@ -302,11 +302,11 @@ mod tests {
         hmap.encode_append(
             &ContentEncoding(vec![Encoding::Identity]));
         hmap.encode_append(
-            &ContentEncoding(vec![Encoding::Gzip, Encoding::Chunked]));
+            &ContentEncoding(vec![Encoding::Gzip, Encoding::Deflate]));
         let ce: ContentEncoding = hmap.decode().unwrap();
         assert_eq!(
             *ce,
-            vec![Encoding::Identity, Encoding::Gzip, Encoding::Chunked]);
+            vec![Encoding::Identity, Encoding::Gzip, Encoding::Deflate]);
     }
 
     fn raw_headers_sample() -> Headers {
@@ -382,17 +382,17 @@ mod tests {
     fn test_value_parse() {
         let mut hmap = http::HeaderMap::new();
         hmap.insert(http::header::CONTENT_ENCODING,
-                    "chunked, gzip".parse().unwrap());
+                    "deflate, gzip".parse().unwrap());
         let val = hmap.get(http::header::CONTENT_ENCODING).unwrap();
         let ce = ContentEncoding::parse_header(&val).unwrap();
-        assert_eq!(ce, ContentEncoding(vec![Encoding::Chunked, Encoding::Gzip]))
+        assert_eq!(ce, ContentEncoding(vec![Encoding::Deflate, Encoding::Gzip]))
     }
 
     #[test]
     fn test_multi_value_parse() {
         let mut hmap = http::HeaderMap::new();
         hmap.insert(http::header::CONTENT_ENCODING,
-                    "chunked, gzip".parse().unwrap());
+                    "deflate, gzip".parse().unwrap());
         hmap.append(http::header::CONTENT_ENCODING,
                     "br".parse().unwrap());
 
@@ -401,7 +401,7 @@ mod tests {
         assert_eq!(
             ce,
             ContentEncoding(vec![
-                Encoding::Chunked, Encoding::Gzip, Encoding::Brotli
+                Encoding::Deflate, Encoding::Gzip, Encoding::Brotli
             ])
         )
     }
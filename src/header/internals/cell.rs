@@ -26,6 +26,11 @@ impl<T> OptCell<T> {
         let opt = &mut *self.0.get();
         opt.as_mut().unwrap()
     }
+
+    #[inline]
+    pub fn into_inner(self) -> Option<T> {
+        self.0.into_inner()
+    }
 }
 
 impl<T> Deref for OptCell<T> {
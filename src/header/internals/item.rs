@@ -4,7 +4,7 @@ use std::fmt;
 use std::str::from_utf8;
 
 use super::cell::{OptCell, PtrMapCell};
-use header::{Header, Formatter, Multi, raw, Raw, RawLike};
+use header::{Header, Formatter, Multi, raw, Raw};
 
 #[derive(Clone)]
 pub struct Item {
@@ -50,6 +50,11 @@ impl Item {
         self.raw.as_ref().unwrap()
     }
 
+    pub fn into_raw(self) -> Raw {
+        self.raw();
+        self.raw.into_inner().expect("item.raw must exist")
+    }
+
     pub fn typed<H: Header + Any>(&self) -> Option<&H> {
         let tid = TypeId::of::<H>();
         match self.typed.get(tid) {
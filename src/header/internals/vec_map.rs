@@ -111,6 +111,16 @@ impl<K: PartialEq, V> VecMap<K, V> {
     }
 }
 
+impl<K, V> IntoIterator for VecMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = ::std::vec::IntoIter<(K, V)>;
+
+    #[inline]
+    fn into_iter(self) -> ::std::vec::IntoIter<(K, V)> {
+        self.vec.into_iter()
+    }
+}
+
 pub enum Entry<'a, K: 'a, V: 'a> {
     Vacant(VacantEntry<'a, K, V>),
     Occupied(OccupiedEntry<'a, K, V>)
@@ -37,19 +37,19 @@
 //!
 //! // Given a HeaderMap with 2 Content-Encoding headers and 3 delimited values
 //! let mut hmap = HeaderMap::new();
-//! hmap.insert(CONTENT_ENCODING, "chunked, gzip".parse()?);
+//! hmap.insert(CONTENT_ENCODING, "deflate, gzip".parse()?);
 //! hmap.append(CONTENT_ENCODING, "identity".parse()?);
 //!
 //! // Parse the first header value
 //! let first = hmap.get(CONTENT_ENCODING).unwrap();
 //! let ce = ContentEncoding::parse_header(&first)?;
-//! assert_eq!(ce, ContentEncoding(vec![Encoding::Chunked, Encoding::Gzip]));
+//! assert_eq!(ce, ContentEncoding(vec![Encoding::Deflate, Encoding::Gzip]));
 //!
 //! // Parse all header values to a single list
 //! let all = hmap.get_all(CONTENT_ENCODING);
 //! let ce = ContentEncoding::parse_header(&all)?;
 //! assert_eq!(ce, ContentEncoding(
-//!     vec![Encoding::Chunked, Encoding::Gzip, Encoding::Identity]
+//!     vec![Encoding::Deflate, Encoding::Gzip, Encoding::Identity]
 //! ));
 //! # Ok(())
 //! # }
@@ -82,11 +82,11 @@
 //! hmap.encode(
 //!     &ContentEncoding(vec![Encoding::Identity]));
 //! hmap.encode_append(
-//!     &ContentEncoding(vec![Encoding::Gzip, Encoding::Chunked]));
+//!     &ContentEncoding(vec![Encoding::Gzip, Encoding::Deflate]));
 //! let ce: ContentEncoding = hmap.decode()?;
 //! assert_eq!(
 //!     *ce,
-//!     vec![Encoding::Identity, Encoding::Gzip, Encoding::Chunked]
+//!     vec![Encoding::Identity, Encoding::Gzip, Encoding::Deflate]
 //! );
 //! # Ok(())
 //! # }
@@ -188,6 +188,9 @@ pub mod parsing;
 #[cfg(feature = "compat")]
 mod compat;
 
+#[cfg(feature = "serde")]
+mod serde_headers;
+
 #[cfg(feature = "compat")]
 pub use self::compat::{TypedHeaders, StandardHeader, ValueMapIter};
 
@@ -212,6 +215,31 @@ pub trait Header: 'static + HeaderClone + Send + Sync {
     fn parse_header<'a, T>(raw: &'a T) -> ::Result<Self>
     where T: RawLike<'a>, Self: Sized;
 
+    /// Parse a header from a raw stream of bytes, also returning any
+    /// remaining bytes which were not parsed as part of the value.
+    ///
+    /// This allows custom combined parsing to continue with the tail of a
+    /// value, such as a trailing comment. The default implementation parses
+    /// the full value via `parse_header`, with an empty remainder.
+    fn parse_header_partial<'a, T>(raw: &'a T) -> ::Result<(Self, &'a [u8])>
+    where T: RawLike<'a>, Self: Sized
+    {
+        Self::parse_header(raw).map(|h| (h, &b""[..]))
+    }
+
+    /// The maximum number of field lines of this header allowed in a
+    /// message, or `None` if it may be repeated.
+    ///
+    /// This is used by `Headers::validate_occurrences`. It is a function
+    /// rather than an associated constant so that `Header` remains usable as
+    /// a trait object. The default is `None`; single value headers, such as
+    /// `Content-Length`, `Host` and `Date`, return `Some(1)`.
+    fn max_occurrences() -> Option<usize>
+    where Self: Sized
+    {
+        None
+    }
+
     /// Format a header to outgoing stream.
     ///
     /// Most headers should be formatted on one line, and so a common pattern
@@ -226,6 +254,35 @@ pub trait Header: 'static + HeaderClone + Send + Sync {
     /// cookie being set be specified in a separate line. Almost every other
     /// case should only format as 1 single line.
     fn fmt_header(&self, f: &mut Formatter) -> fmt::Result;
+
+    /// Format just the header value, without the name or line ending.
+    ///
+    /// This is for callers needing the value alone, such as for
+    /// `http::HeaderMap::insert`. A value formatted over multiple lines is
+    /// folded into one, separated by `, `. Such folding is not allowed for
+    /// `Set-Cookie`, which must use `to_value_strings` instead.
+    fn to_value_string(&self) -> String {
+        HeaderValueString(self).to_string()
+    }
+
+    /// Format just the header value, as a string for each line, without
+    /// folding.
+    ///
+    /// This is for headers such as `Set-Cookie`, where each line must be
+    /// used as its own value, such as with `http::HeaderMap::append`.
+    fn to_value_strings(&self) -> Vec<String> {
+        let mut lines = raw::new();
+        self.fmt_header(&mut Formatter(Multi::Raw(&mut lines))).expect("fmt failed");
+        lines.iter().map(|line| String::from_utf8_lossy(line).into_owned()).collect()
+    }
+}
+
+struct HeaderValueString<'a, H: Header + ?Sized + 'a>(&'a H);
+
+impl<'a, H: Header + ?Sized + 'a> fmt::Display for HeaderValueString<'a, H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_header(&mut Formatter(Multi::Join(true, f)))
+    }
 }
 
 mod sealed {
@@ -458,6 +515,67 @@ literals! {
     27 => AccessControlAllowOrigin;
 }
 
+// A typed header which may be looked up by name alone, without knowing its
+// type at compile time.
+struct Registered {
+    name: fn() -> &'static str,
+    max_occurrences: fn() -> Option<usize>,
+    validate: fn(&Raw) -> ::Result<()>,
+    fmt_debug: fn(&Raw, &mut fmt::Formatter) -> Option<fmt::Result>,
+}
+
+fn validate<H: Header>(raw: &Raw) -> ::Result<()> {
+    H::parse_header(raw).map(|_| ())
+}
+
+fn fmt_debug<H: Header + fmt::Debug>(raw: &Raw, f: &mut fmt::Formatter)
+    -> Option<fmt::Result>
+{
+    H::parse_header(raw).ok().map(|h| fmt::Debug::fmt(&h, f))
+}
+
+macro_rules! registered {
+    ($($header:ty),+) => (
+        static REGISTERED: &'static [Registered] = &[
+            $(
+            Registered {
+                name: <$header as Header>::header_name,
+                max_occurrences: <$header as Header>::max_occurrences,
+                validate: validate::<$header>,
+                fmt_debug: fmt_debug::<$header>,
+            },
+            )+
+        ];
+    );
+}
+
+// The typed headers of this crate, looked up by name to apply their
+// `max_occurrences` limit and validation, and to format them for `Debug`.
+// `standard_header!` generates a test that its header is listed here.
+registered! {
+    Accept, AcceptCharset, AcceptEncoding, AcceptLanguage, AcceptPatch,
+    AcceptPost, AcceptRanges,
+    AccessControlAllowCredentials, AccessControlAllowHeaders,
+    AccessControlAllowMethods, AccessControlAllowOrigin,
+    AccessControlExposeHeaders, AccessControlMaxAge,
+    AccessControlRequestHeaders, AccessControlRequestMethod, Age, Allow,
+    CacheControl, ClearSiteData, Connection, ContentDisposition, ContentEncoding,
+    ContentLanguage, ContentLength, ContentLocation, ContentRange,
+    ContentSecurityPolicy, ContentType,
+    Cookie, Date, ETag, Expect, Expires, FeaturePolicy, Forwarded, From, Host, IfMatch, IfModifiedSince,
+    IfNoneMatch, IfRange, IfUnmodifiedSince, KeepAlive, LastEventId, LastModified, Link, Location,
+    MaxForwards, Origin, PermissionsPolicy, Pragma, Prefer, PreferenceApplied, Priority, Range,
+    Referer, ReferrerPolicy, RetryAfter,
+    SecWebSocketExtensions, SecWebSocketKey, SecWebSocketProtocol,
+    SecWebSocketVersion, Server,
+    SetCookie, StrictTransportSecurity, Te, TransferEncoding, Upgrade,
+    UserAgent, Vary, Via, WantDigest, Warning, XForwardedFor, XForwardedHost, XForwardedProto
+}
+
+fn registered(name: &str) -> Option<&'static Registered> {
+    REGISTERED.iter().find(|r| Ascii::new((r.name)()) == Ascii::new(name))
+}
+
 impl Headers {
 
     /// Creates a new, empty headers map.
@@ -482,6 +600,27 @@ impl Headers {
                          Item::new_typed(value));
     }
 
+    /// Set a header field from an already formatted value, without
+    /// formatting a typed header.
+    ///
+    /// The field is determined by the header type. The value is neither
+    /// parsed nor validated here, but is parsed as `H` on a later `get`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use hyperx::header::{Headers, CacheControl, CacheDirective};
+    /// let mut headers = Headers::new();
+    /// headers.set_formatted::<CacheControl>("public, max-age=60");
+    /// assert_eq!(headers.get_raw("cache-control").unwrap(), "public, max-age=60");
+    /// assert_eq!(headers.get::<CacheControl>(), Some(&CacheControl(vec![
+    ///     CacheDirective::Public, CacheDirective::MaxAge(60)])));
+    /// ```
+    pub fn set_formatted<H: Header>(&mut self, value: &str) {
+        self.data.insert(HeaderName(Ascii::new(Cow::Borrowed(header_name::<H>()))),
+                         Item::new_raw(Raw::from(value.to_owned())));
+    }
+
     /// Get a reference to the header field's value, if it exists.
     pub fn get<H: Header>(&self) -> Option<&H> {
         self.data.get(&HeaderName(Ascii::new(Cow::Borrowed(header_name::<H>()))))
@@ -526,11 +665,216 @@ impl Headers {
         }
     }
 
+    /// Returns an iterator over the header fields, ordered by name.
+    ///
+    /// Names are compared case-insensitively, so the order is independent of
+    /// both insertion order and the casing used when each header was set.
+    /// Useful wherever output must be stable, such as in tests, signatures or
+    /// cache keys.
+    pub fn sorted_iter(&self) -> SortedHeadersItems {
+        let mut items = self.data.iter().collect::<Vec<_>>();
+        items.sort_by(|a, b| {
+            let a = a.0.as_ref().bytes().map(|c| c.to_ascii_lowercase());
+            let b = b.0.as_ref().bytes().map(|c| c.to_ascii_lowercase());
+            a.cmp(b)
+        });
+        SortedHeadersItems {
+            inner: items.into_iter()
+        }
+    }
+
     /// Returns the number of headers in the map.
     pub fn len(&self) -> usize {
         self.data.len()
     }
 
+    /// Clone only the headers whose names satisfy the predicate into a new
+    /// `Headers` map.
+    ///
+    /// Useful for building an outbound request from a subset of the headers
+    /// of an inbound one, without cloning headers that are then discarded.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use hyperx::header::{Headers, ContentLength, Host};
+    /// let mut headers = Headers::new();
+    /// headers.set(ContentLength(10));
+    /// headers.set(Host::new("example.com", None));
+    ///
+    /// let forwarded = headers.clone_filtered(|name| name.eq_ignore_ascii_case("content-length"));
+    /// assert_eq!(forwarded.len(), 1);
+    /// assert_eq!(forwarded.get(), Some(&ContentLength(10)));
+    /// ```
+    pub fn clone_filtered<F: Fn(&str) -> bool>(&self, keep: F) -> Headers {
+        let mut headers = Headers::new();
+        for &(ref name, ref item) in self.data.iter() {
+            if keep(name.as_ref()) {
+                headers.data.append(name.clone(), item.clone());
+            }
+        }
+        headers
+    }
+
+    /// Check that the `Upgrade` and `Connection` headers agree, as required
+    /// for a protocol switch such as a WebSocket or h2c handshake.
+    ///
+    /// If either an `Upgrade` header or the `upgrade` option of `Connection`
+    /// is present, then so must the other be.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use hyperx::header::{Headers, Upgrade, Protocol, ProtocolName, UpgradeMismatch};
+    /// let mut headers = Headers::new();
+    /// headers.set(Upgrade(vec![Protocol::new(ProtocolName::WebSocket, None)]));
+    /// assert_eq!(headers.check_upgrade(), Err(UpgradeMismatch::MissingConnectionUpgrade));
+    ///
+    /// headers.set_raw("Connection", "keep-alive, Upgrade");
+    /// assert_eq!(headers.check_upgrade(), Ok(()));
+    /// ```
+    pub fn check_upgrade(&self) -> ::std::result::Result<(), UpgradeMismatch> {
+        let connection_upgrade = self.get::<Connection>()
+            .map_or(false, |conn| conn.contains_header_name("upgrade"));
+        match (self.has::<Upgrade>(), connection_upgrade) {
+            (true, false) => Err(UpgradeMismatch::MissingConnectionUpgrade),
+            (false, true) => Err(UpgradeMismatch::MissingUpgrade),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that no standard header appears on more field lines than it
+    /// allows, returning the names of any that do.
+    ///
+    /// A server may use this to reject a request which is ambiguous, such as
+    /// one with two `Host` lines. Headers which may be repeated, and any
+    /// unknown headers, are not limited. See `Header::max_occurrences`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use hyperx::header::Headers;
+    /// let mut headers = Headers::new();
+    /// headers.append_raw("Host", "example.com");
+    /// assert_eq!(headers.validate_occurrences(), Ok(()));
+    ///
+    /// headers.append_raw("Host", "example.org");
+    /// assert_eq!(headers.validate_occurrences(), Err(vec!["Host"]));
+    /// ```
+    pub fn validate_occurrences(&self) -> ::std::result::Result<(), Vec<&str>> {
+        let exceeded = self.data.iter()
+            .filter(|&&(ref name, ref item)| {
+                registered(name.as_ref())
+                    .and_then(|r| (r.max_occurrences)())
+                    .map_or(false, |max| item.raw().len() > max)
+            })
+            .map(|&(ref name, _)| name.as_ref())
+            .collect::<Vec<&str>>();
+        if exceeded.is_empty() {
+            Ok(())
+        } else {
+            Err(exceeded)
+        }
+    }
+
+    /// Parse every standard header with its typed parser, returning the name
+    /// and parse error of each which fails.
+    ///
+    /// A server may use this to reject a request with any malformed known
+    /// header up front. Unknown headers are skipped.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use hyperx::header::Headers;
+    /// let mut headers = Headers::new();
+    /// headers.set_raw("Content-Length", "10");
+    /// headers.set_raw("X-Custom", "anything");
+    /// assert!(headers.validate_all().is_empty());
+    ///
+    /// headers.set_raw("Content-Length", "ten");
+    /// let errors = headers.validate_all();
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].0, "Content-Length");
+    /// ```
+    pub fn validate_all(&self) -> Vec<(String, ::Error)> {
+        self.data.iter()
+            .filter_map(|&(ref name, ref item)| {
+                registered(name.as_ref())
+                    .and_then(|r| (r.validate)(item.raw()).err())
+                    .map(|err| (name.as_ref().to_owned(), err))
+            })
+            .collect()
+    }
+
+    /// Check that `Content-Encoding` does not compress a `Content-Type` which
+    /// is already compressed, such as `application/gzip`.
+    ///
+    /// This returns a warning, not an error. See
+    /// `ContentEncoding::double_compression`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use hyperx::header::Headers;
+    /// let mut headers = Headers::new();
+    /// headers.set_raw("Content-Type", "application/gzip");
+    /// assert_eq!(headers.check_content_coding(), None);
+    ///
+    /// headers.set_raw("Content-Encoding", "gzip");
+    /// assert!(headers.check_content_coding().is_some());
+    /// ```
+    pub fn check_content_coding(&self) -> Option<DoubleCompression> {
+        match (self.get::<ContentEncoding>(), self.get::<ContentType>()) {
+            (Some(encoding), Some(content_type)) => encoding.double_compression(content_type),
+            _ => None,
+        }
+    }
+
+    /// Set the message framing headers of a response for a body of known or
+    /// unknown length, removing any conflicting header.
+    ///
+    /// A known length sets `Content-Length` and removes `Transfer-Encoding`.
+    /// An unknown length, as for a streaming body, removes `Content-Length`
+    /// and ensures `chunked` is the final `Transfer-Encoding`, keeping any
+    /// other transfer codings already applied.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use hyperx::header::{Headers, ContentLength, TransferEncoding};
+    /// let mut headers = Headers::new();
+    /// headers.set_body_length(Some(42));
+    /// assert_eq!(headers.get(), Some(&ContentLength(42)));
+    ///
+    /// headers.set_body_length(None);
+    /// assert_eq!(headers.get(), Some(&TransferEncoding::chunked()));
+    /// assert!(!headers.has::<ContentLength>());
+    /// ```
+    pub fn set_body_length(&mut self, length: Option<u64>) {
+        match length {
+            Some(len) => {
+                self.remove_raw(header_name::<TransferEncoding>());
+                self.set(ContentLength(len));
+            },
+            None => {
+                self.remove_raw(header_name::<ContentLength>());
+                let chunked = match self.get_mut::<TransferEncoding>() {
+                    Some(te) => {
+                        if te.last() != Some(&Encoding::Chunked) {
+                            te.push(Encoding::Chunked);
+                        }
+                        true
+                    },
+                    None => false,
+                };
+                if !chunked {
+                    self.set(TransferEncoding::chunked());
+                }
+            },
+        }
+    }
+
     /// Remove all headers from the map.
     pub fn clear(&mut self) {
         self.data.clear()
@@ -555,6 +899,20 @@ impl Headers {
             .map(Item::raw)
     }
 
+    /// Access the first raw value of a header as bytes.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use hyperx::header::Headers;
+    /// # let mut headers = Headers::new();
+    /// headers.set_raw("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ==");
+    /// assert_eq!(headers.get_bytes("Sec-WebSocket-Key"), Some(&b"dGhlIHNhbXBsZSBub25jZQ=="[..]));
+    /// ```
+    pub fn get_bytes(&self, name: &str) -> Option<&[u8]> {
+        self.get_raw(name).and_then(|raw| raw.iter().next())
+    }
+
     /// Set the raw value of a header, bypassing any typed headers.
     ///
     /// Example:
@@ -616,6 +974,25 @@ impl Headers {
         self.data.remove(name);
     }
 
+    /// Returns a view of the headers whose `Debug` output shows the parsed,
+    /// typed value of each standard header.
+    ///
+    /// Headers which are not standard, or which fail to parse, are shown
+    /// with their raw value, as with the `Debug` output of `Headers` itself.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use hyperx::header::Headers;
+    /// # let mut headers = Headers::new();
+    /// headers.set_raw("content-length", "10");
+    /// assert_eq!(
+    ///     format!("{:?}", headers.debug_typed()),
+    ///     r#"{"content-length": ContentLength(10)}"#);
+    /// ```
+    pub fn debug_typed(&self) -> DebugTyped {
+        DebugTyped(self)
+    }
 }
 
 impl PartialEq for Headers {
@@ -653,6 +1030,31 @@ impl fmt::Debug for Headers {
     }
 }
 
+/// A `Debug` view of `Headers` showing parsed values, returned by
+/// `Headers::debug_typed`.
+pub struct DebugTyped<'a>(&'a Headers);
+
+impl<'a> fmt::Debug for DebugTyped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|view| (view.0.as_ref(), TypedValue(view))))
+            .finish()
+    }
+}
+
+struct TypedValue<'a>(HeaderView<'a>);
+
+impl<'a> fmt::Debug for TypedValue<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(reg) = registered(self.0.name()) {
+            if let Some(res) = (reg.fmt_debug)(self.0.raw(), f) {
+                return res;
+            }
+        }
+        fmt::Debug::fmt(&ValueString((self.0).1), f)
+    }
+}
+
 /// An `Iterator` over the fields in a `Headers` map.
 #[allow(missing_debug_implementations)]
 pub struct HeadersItems<'a> {
@@ -667,6 +1069,55 @@ impl<'a> Iterator for HeadersItems<'a> {
     }
 }
 
+/// An `Iterator` over the fields in a `Headers` map, ordered by name.
+///
+/// Returned by `Headers::sorted_iter`.
+#[allow(missing_debug_implementations)]
+pub struct SortedHeadersItems<'a> {
+    inner: ::std::vec::IntoIter<&'a (HeaderName, Item)>
+}
+
+impl<'a> Iterator for SortedHeadersItems<'a> {
+    type Item = HeaderView<'a>;
+
+    fn next(&mut self) -> Option<HeaderView<'a>> {
+        self.inner.next().map(|&(ref k, ref v)| HeaderView(k, v))
+    }
+}
+
+/// A consuming `Iterator` over the fields in a `Headers` map.
+///
+/// Returned by `Headers::into_iter`, yielding each field name along with its
+/// raw value.
+#[allow(missing_debug_implementations)]
+pub struct HeadersIntoIter {
+    inner: ::std::vec::IntoIter<(HeaderName, Item)>
+}
+
+impl Iterator for HeadersIntoIter {
+    type Item = (String, Raw);
+
+    fn next(&mut self) -> Option<(String, Raw)> {
+        self.inner.next().map(|(name, item)| {
+            (name.0.into_inner().into_owned(), item.into_raw())
+        })
+    }
+}
+
+impl IntoIterator for Headers {
+    type Item = (String, Raw);
+    type IntoIter = HeadersIntoIter;
+
+    /// Consumes the map, returning an iterator over the owned header fields.
+    ///
+    /// Typed values are formatted into their `Raw` form.
+    fn into_iter(self) -> HeadersIntoIter {
+        HeadersIntoIter {
+            inner: self.data.into_iter()
+        }
+    }
+}
+
 /// Returned with the `HeadersItems` iterator.
 pub struct HeaderView<'a>(&'a HeaderName, &'a Item);
 
@@ -799,8 +1250,10 @@ impl PartialEq<HeaderName> for str {
 mod tests {
     use std::fmt;
     use super::{
-        Headers, Header, RawLike, ContentLength, ContentType, Host,
-        SetCookie};
+        Headers, Header, Raw, RawLike, ContentLength, ContentType, Host,
+        SetCookie, Connection, Upgrade, Protocol, ProtocolName, UpgradeMismatch,
+        TransferEncoding, Encoding, CacheControl, CacheDirective, ConnectionOption,
+        Date};
 
     #[cfg(feature = "nightly")]
     use test::Bencher;
@@ -856,6 +1309,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_header_partial_default() {
+        let r: Raw = "10".into();
+        let (len, rest) = ContentLength::parse_header_partial(&r).unwrap();
+        assert_eq!(len, ContentLength(10));
+        assert!(rest.is_empty());
+    }
+
     #[test]
     fn test_different_structs_for_same_header() {
         let headers = make_header!(b"Content-Length: 10");
@@ -926,6 +1387,22 @@ mod tests {
         assert_eq!(s, "Content-Length: 10\r\nx-foo: foo\r\nx-foo: bar\r\n");
     }
 
+    #[test]
+    fn test_debug_typed() {
+        let mut headers = Headers::new();
+        headers.set_raw("Cache-Control", "max-age=60, public");
+        headers.set_raw("Content-Length", "ten");
+        headers.set_raw("x-foo", vec![b"foo".to_vec(), b"bar".to_vec()]);
+        assert_eq!(
+            format!("{:?}", headers),
+            r#"{"Cache-Control": "max-age=60, public", "#.to_owned() +
+            r#""Content-Length": "ten", "x-foo": "foo, bar"}"#);
+        assert_eq!(
+            format!("{:?}", headers.debug_typed()),
+            r#"{"Cache-Control": CacheControl([MaxAge(60), Public]), "#.to_owned() +
+            r#""Content-Length": "ten", "x-foo": "foo, bar"}"#);
+    }
+
     #[test]
     fn test_set_raw() {
         let mut headers = Headers::new();
@@ -935,6 +1412,32 @@ mod tests {
         assert_eq!(headers.get(), Some(&ContentLength(20)));
     }
 
+    #[test]
+    fn test_set_formatted() {
+        let mut headers = Headers::new();
+        headers.set_raw("content-length", "5");
+        headers.set_formatted::<ContentLength>("10");
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers.get_raw("Content-Length").unwrap(), "10");
+        assert_eq!(headers.get(), Some(&ContentLength(10)));
+        assert_eq!(headers.to_string(), "Content-Length: 10\r\n");
+
+        headers.set_formatted::<ContentType>("not a mime");
+        assert_eq!(headers.get_raw("content-type").unwrap(), "not a mime");
+        assert_eq!(headers.get::<ContentType>(), None);
+    }
+
+    #[test]
+    fn test_get_bytes() {
+        let mut headers = Headers::new();
+        headers.set(ContentLength(10));
+        headers.append_raw("x-foo", b"\x00\xff".to_vec());
+        headers.append_raw("x-foo", "bar");
+        assert_eq!(headers.get_bytes("content-length"), Some(&b"10"[..]));
+        assert_eq!(headers.get_bytes("X-Foo"), Some(&b"\x00\xff"[..]));
+        assert_eq!(headers.get_bytes("x-bar"), None);
+    }
+
     #[test]
     fn test_append_raw() {
         let mut headers = Headers::new();
@@ -978,6 +1481,160 @@ mod tests {
         assert_eq!(headers.len(), 2);
     }
 
+    #[test]
+    fn test_sorted_iter() {
+        let mut headers = Headers::new();
+        headers.set(ContentLength(10));
+        headers.set_raw("x-custom", "a");
+        headers.set(Host::new("example.com", None));
+        headers.set_raw("Accept", "*/*");
+        headers.set(ContentType::json());
+        let names = headers.sorted_iter().map(|h| h.name()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["Accept", "Content-Length", "Content-Type", "Host", "x-custom"]);
+    }
+
+    #[test]
+    fn test_to_value_string() {
+        let cache_control = CacheControl(vec![
+            CacheDirective::NoCache,
+            CacheDirective::MaxAge(60),
+        ]);
+        assert_eq!(cache_control.to_value_string(), "no-cache, max-age=60");
+
+        let connection = Connection(vec![
+            ConnectionOption::KeepAlive,
+            ConnectionOption::ConnectionHeader("Upgrade".parse().unwrap()),
+        ]);
+        let header: &Header = &connection;
+        assert_eq!(header.to_value_string(), "keep-alive, Upgrade");
+
+        assert_eq!(header.to_value_strings(), vec!["keep-alive, Upgrade"]);
+
+        let set_cookie = SetCookie(vec!["a=1".to_owned(), "b=2".to_owned()]);
+        assert_eq!(set_cookie.to_value_strings(), vec!["a=1", "b=2"]);
+    }
+
+    #[test]
+    fn test_validate_all() {
+        let mut headers = Headers::new();
+        headers.set_raw("Cache-Control", vec![b"max-age=\xff".to_vec()]);
+        headers.set_raw("Host", "example.com");
+        headers.set_raw("X-Unknown", vec![b"\xff".to_vec()]);
+        let errors = headers.validate_all();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "Cache-Control");
+        match errors[0].1 {
+            ::Error::Utf8(_) => (),
+            ref err => panic!("unexpected error: {:?}", err),
+        }
+
+        headers.set(CacheControl(vec![CacheDirective::NoCache]));
+        assert!(headers.validate_all().is_empty());
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let mut headers = Headers::new();
+        headers.set(ContentLength(10));
+        headers.set_raw("x-custom", vec![b"a".to_vec(), b"b".to_vec()]);
+        let fields = headers.into_iter().collect::<Vec<_>>();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].0, "Content-Length");
+        assert_eq!(fields[0].1, *"10");
+        assert_eq!(fields[1].0, "x-custom");
+        assert_eq!(fields[1].1, Raw::from(vec![b"a".to_vec(), b"b".to_vec()]));
+    }
+
+    #[test]
+    fn test_clone_filtered() {
+        let mut headers = Headers::new();
+        headers.set(ContentLength(10));
+        headers.set_raw("X-Internal", "secret");
+        headers.set(ContentType::json());
+        headers.set_raw("accept", "*/*");
+
+        let whitelist = ["content-type", "accept", "user-agent"];
+        let filtered = headers.clone_filtered(|name| {
+            whitelist.iter().any(|w| w.eq_ignore_ascii_case(name))
+        });
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(filtered.get(), Some(&ContentType::json()));
+        assert_eq!(filtered.get_raw("Accept").unwrap(), "*/*");
+        assert!(!filtered.has::<ContentLength>());
+        assert!(filtered.get_raw("x-internal").is_none());
+        // the original is untouched
+        assert_eq!(headers.len(), 4);
+    }
+
+    #[test]
+    fn test_check_upgrade() {
+        let mut headers = Headers::new();
+        assert_eq!(headers.check_upgrade(), Ok(()));
+
+        headers.set(Upgrade(vec![Protocol::new(ProtocolName::WebSocket, None)]));
+        assert_eq!(headers.check_upgrade(), Err(UpgradeMismatch::MissingConnectionUpgrade));
+        headers.set(Connection::keep_alive());
+        assert_eq!(headers.check_upgrade(), Err(UpgradeMismatch::MissingConnectionUpgrade));
+
+        headers.set_raw("Connection", "UPGRADE");
+        assert_eq!(headers.check_upgrade(), Ok(()));
+
+        headers.remove::<Upgrade>();
+        assert_eq!(headers.check_upgrade(), Err(UpgradeMismatch::MissingUpgrade));
+    }
+
+    #[test]
+    fn test_validate_occurrences() {
+        let mut headers = Headers::new();
+        headers.append_raw("Host", b"example.com".to_vec());
+        headers.append_raw("Cache-Control", b"no-cache".to_vec());
+        headers.append_raw("Cache-Control", b"max-age=0".to_vec());
+        headers.append_raw("X-Custom", b"a".to_vec());
+        headers.append_raw("X-Custom", b"b".to_vec());
+        assert_eq!(headers.validate_occurrences(), Ok(()));
+        assert_eq!(headers.get::<CacheControl>().unwrap().len(), 2);
+
+        headers.append_raw("host", b"example.org".to_vec());
+        headers.append_raw("Date", b"Sun, 06 Nov 1994 08:49:37 GMT".to_vec());
+        headers.append_raw("Date", b"Sun, 06 Nov 1994 08:49:37 GMT".to_vec());
+        assert_eq!(headers.validate_occurrences(), Err(vec!["Host", "Date"]));
+
+        // typed values count as a single line
+        headers.set(Host::new("example.com", None));
+        headers.set(ContentLength(10));
+        headers.remove::<Date>();
+        assert_eq!(headers.validate_occurrences(), Ok(()));
+    }
+
+    #[test]
+    fn test_set_body_length_known() {
+        let mut headers = Headers::new();
+        headers.set(TransferEncoding::chunked());
+        headers.set(ContentType::json());
+        headers.set_body_length(Some(1024));
+        assert_eq!(headers.get(), Some(&ContentLength(1024)));
+        assert!(!headers.has::<TransferEncoding>());
+        assert!(headers.has::<ContentType>());
+    }
+
+    #[test]
+    fn test_set_body_length_unknown() {
+        let mut headers = Headers::new();
+        headers.set(ContentLength(10));
+        headers.set_body_length(None);
+        assert!(!headers.has::<ContentLength>());
+        assert_eq!(headers.get(), Some(&TransferEncoding::chunked()));
+
+        // already chunked is left alone
+        headers.set_body_length(None);
+        assert_eq!(headers.get_raw("transfer-encoding").unwrap(), "chunked");
+
+        // chunked is added as the final coding
+        headers.set_raw("Transfer-Encoding", "gzip");
+        headers.set_body_length(None);
+        assert_eq!(headers.get(), Some(&TransferEncoding(vec![Encoding::Gzip, Encoding::Chunked])));
+    }
+
     #[test]
     fn test_clear() {
         let mut headers = Headers::new();
@@ -4,6 +4,9 @@ use language_tags::LanguageTag;
 use std::str;
 use std::str::FromStr;
 use std::fmt::{self, Display};
+use std::time::Duration;
+use std::u32;
+use base64;
 use percent_encoding;
 
 use header::RawLike;
@@ -28,24 +31,275 @@ pub fn from_raw_str<T: str::FromStr>(raw: &[u8]) -> ::Result<T> {
     T::from_str(s).or(Err(::Error::Header))
 }
 
+/// Convert a `Duration` to whole delta-seconds, as carried by headers such as
+/// `Age` and `Cache-Control: max-age`.
+///
+/// Fractional seconds are rounded to the nearest second, with halves rounded
+/// up, and durations beyond `u32::MAX` seconds saturate.
+pub fn duration_to_secs_saturating(duration: Duration) -> u32 {
+    let mut secs = duration.as_secs();
+    if duration.subsec_nanos() >= 500_000_000 {
+        secs = secs.saturating_add(1);
+    }
+    if secs > u64::from(u32::MAX) {
+        u32::MAX
+    } else {
+        secs as u32
+    }
+}
+
+/// Encode bytes as a structured field byte sequence, defined in
+/// [RFC8941](https://tools.ietf.org/html/rfc8941#section-3.3.5): padded
+/// base64 between `:` delimiters.
+///
+/// ```
+/// use hyperx::header::parsing::encode_byte_sequence;
+///
+/// assert_eq!(encode_byte_sequence(b"hello"), ":aGVsbG8=:");
+/// ```
+pub fn encode_byte_sequence(bytes: &[u8]) -> String {
+    format!(":{}:", base64::encode(bytes))
+}
+
+/// Decode a structured field byte sequence, defined in
+/// [RFC8941](https://tools.ietf.org/html/rfc8941#section-4.2.7).
+///
+/// The value must be enclosed in `:` delimiters and contain only base64
+/// characters, with any `=` padding at the end. As the RFC recommends,
+/// missing padding is accepted.
+pub fn decode_byte_sequence(s: &str) -> ::Result<Vec<u8>> {
+    let bytes = s.as_bytes();
+    if bytes.len() < 2 || bytes[0] != b':' || bytes[bytes.len() - 1] != b':' {
+        return Err(::Error::Header);
+    }
+    let encoded = &bytes[1..bytes.len() - 1];
+    let padding = encoded.iter().rev().take_while(|&&b| b == b'=').count();
+    let data = &encoded[..encoded.len() - padding];
+    if padding > 2 || !data.iter().all(|&b| is_base64_char(b)) {
+        return Err(::Error::Header);
+    }
+    base64::decode(data).map_err(|_| ::Error::Header)
+}
+
+fn is_base64_char(b: u8) -> bool {
+    match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'+' | b'/' => true,
+        _ => false,
+    }
+}
+
 /// Reads a comma-delimited raw header into a Vec.
+///
+/// Lines are split on `,` as bytes, except within a quoted-string, and only
+/// each trimmed, non-empty element is validated as UTF-8. An element that is
+/// not valid UTF-8 results in an `Error::Utf8`; elements that fail to parse as
+/// `T` are skipped.
+///
+/// With `T` as a `QualityItem`, this parses a list of items with optional
+/// `;q=` weights, as used by `Accept-Encoding`, `Accept-Language`, `TE` and
+/// `Want-Digest`. Items with a malformed weight are skipped.
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::{Raw, QualityItem, q, qitem};
+/// use hyperx::header::parsing::from_comma_delimited;
+///
+/// let raw: Raw = "gzip;q=0.5, br, identity;q=2".into();
+/// let codings: Vec<QualityItem<String>> = from_comma_delimited(&raw).unwrap();
+/// assert_eq!(codings, vec![
+///     QualityItem::new("gzip".to_owned(), q(500)),
+///     qitem("br".to_owned()),
+/// ]);
+/// ```
 #[inline]
 pub fn from_comma_delimited<'a, R, T>(raw: &'a R) -> ::Result<Vec<T>>
 where R: RawLike<'a>, T: str::FromStr
 {
     let mut result = Vec::new();
-    for s in raw.iter() {
-        let s = try!(str::from_utf8(s.as_ref()));
-        result.extend(s.split(',')
-                      .filter_map(|x| match x.trim() {
-                          "" => None,
-                          y => Some(y)
-                      })
-                      .filter_map(|x| x.trim().parse().ok()))
+    for item in comma_delimited_iter(raw) {
+        if let Ok(x) = try!(item).trim().parse() {
+            result.push(x);
+        }
     }
     Ok(result)
 }
 
+/// Reads a comma-delimited raw header into a Vec, as by
+/// `from_comma_delimited`, but failing if any item does not satisfy `valid`.
+pub fn from_comma_delimited_valid<'a, R, T, F>(raw: &'a R, valid: F)
+    -> ::Result<Vec<T>>
+where R: RawLike<'a>, T: str::FromStr, F: Fn(&T) -> bool
+{
+    let items = try!(from_comma_delimited(raw));
+    if !items.iter().all(valid) {
+        Err(::Error::Header)
+    } else {
+        Ok(items)
+    }
+}
+
+/// Lazily iterate the elements of a comma-delimited raw header.
+///
+/// Elements are split and trimmed exactly as by `from_comma_delimited`, with
+/// empty elements skipped, but without allocating. This allows a caller to
+/// stop at the first element of interest.
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::Raw;
+/// use hyperx::header::parsing::comma_delimited_iter;
+///
+/// let raw: Raw = "Upgrade, , close".into();
+/// let close = comma_delimited_iter(&raw)
+///     .any(|item| item.map(|s| s.eq_ignore_ascii_case("close")).unwrap_or(false));
+/// assert!(close);
+/// ```
+pub fn comma_delimited_iter<'a, R>(raw: &'a R) -> impl Iterator<Item = ::Result<&'a str>> + 'a
+where R: RawLike<'a>
+{
+    raw.iter()
+        .flat_map(|line| CommaDelimited { rest: Some(line) })
+        .map(trim_ascii_ws)
+        .filter(|item| !item.is_empty())
+        .map(|item| str::from_utf8(item).map_err(::Error::from))
+}
+
+// Splits on commas outside of quoted-strings, honoring `\` quoted-pairs.
+struct CommaDelimited<'a> {
+    rest: Option<&'a [u8]>,
+}
+
+impl<'a> Iterator for CommaDelimited<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        self.rest.take().map(|line| {
+            let (item, rest) = split_first_element(line);
+            self.rest = rest;
+            item
+        })
+    }
+}
+
+fn split_first_element(line: &[u8]) -> (&[u8], Option<&[u8]>) {
+    match find_unquoted(line, b',') {
+        Some(i) => (&line[..i], Some(&line[i + 1..])),
+        None => (line, None),
+    }
+}
+
+fn find_unquoted(line: &[u8], delim: u8) -> Option<usize> {
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, b) in line.iter().enumerate() {
+        if escaped {
+            escaped = false;
+        } else if quoted {
+            match *b {
+                b'\\' => escaped = true,
+                b'"' => quoted = false,
+                _ => (),
+            }
+        } else if *b == b'"' {
+            quoted = true;
+        } else if *b == delim {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Split a value on an ASCII delimiter, such as the `;` between parameters,
+/// except within quoted-strings, honoring `\` quoted-pairs.
+///
+/// Unlike `comma_delimited_iter`, elements are neither trimmed nor skipped
+/// when empty.
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::parsing::split_unquoted;
+///
+/// let parts: Vec<_> = split_unquoted("x; note=\"a;b\"", b';').collect();
+/// assert_eq!(parts, vec!["x", " note=\"a;b\""]);
+/// ```
+pub fn split_unquoted<'a>(s: &'a str, delim: u8) -> impl Iterator<Item = &'a str> + 'a {
+    SplitUnquoted { rest: Some(s), delim: delim }
+}
+
+struct SplitUnquoted<'a> {
+    rest: Option<&'a str>,
+    delim: u8,
+}
+
+impl<'a> Iterator for SplitUnquoted<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.rest.take().map(|s| {
+            match find_unquoted(s.as_bytes(), self.delim) {
+                Some(i) => {
+                    self.rest = Some(&s[i + 1..]);
+                    &s[..i]
+                },
+                None => s,
+            }
+        })
+    }
+}
+
+/// Remove the `\` of each quoted-pair in the content of a quoted-string,
+/// with its surrounding quotes already removed.
+pub fn unescape(value: &str) -> String {
+    let mut s = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(c) = chars.next() {
+                s.push(c);
+            }
+        } else {
+            s.push(c);
+        }
+    }
+    s
+}
+
+/// Returns true if `s` is a `token`, as defined in
+/// [RFC7230](https://tools.ietf.org/html/rfc7230#section-3.2.6).
+///
+/// ```text
+/// token = 1*tchar
+/// tchar = "!" / "#" / "$" / "%" / "&" / "'" / "*" / "+" / "-" / "." /
+///         "^" / "_" / "`" / "|" / "~" / DIGIT / ALPHA
+/// ```
+pub fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+fn trim_ascii_ws(mut bytes: &[u8]) -> &[u8] {
+    while let Some((first, rest)) = bytes.split_first() {
+        if !is_ascii_ws(*first) {
+            break;
+        }
+        bytes = rest;
+    }
+    while let Some((last, rest)) = bytes.split_last() {
+        if !is_ascii_ws(*last) {
+            break;
+        }
+        bytes = rest;
+    }
+    bytes
+}
+
+#[inline]
+fn is_ascii_ws(b: u8) -> bool {
+    b == b' ' || b == b'\t' || b == b'\r' || b == b'\n'
+}
+
 /// Format an array into a comma-delimited string.
 pub fn fmt_comma_delimited<T: Display>(f: &mut fmt::Formatter, parts: &[T]) -> fmt::Result {
     let mut iter = parts.iter();
@@ -59,6 +313,22 @@ pub fn fmt_comma_delimited<T: Display>(f: &mut fmt::Formatter, parts: &[T]) -> f
     Ok(())
 }
 
+/// Format a value as a `token` if it is one, otherwise as a `quoted-string`,
+/// escaping `"` and `\` so that `unescape` reads back the same value.
+pub fn fmt_token_or_quoted(f: &mut fmt::Formatter, value: &str) -> fmt::Result {
+    if is_token(value) {
+        return f.write_str(value);
+    }
+    try!(f.write_str("\""));
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            try!(f.write_str("\\"));
+        }
+        try!(write!(f, "{}", c));
+    }
+    f.write_str("\"")
+}
+
 /// An extended header parameter value (i.e., tagged with a character set and optionally,
 /// a language), as defined in [RFC 5987](https://tools.ietf.org/html/rfc5987#section-3.2).
 #[derive(Clone, Debug, PartialEq)]
@@ -153,6 +423,80 @@ impl Display for ExtendedValue {
     }
 }
 
+/// Encoding and decoding of `ext-value` strings, as defined in
+/// [RFC 8187](https://tools.ietf.org/html/rfc8187#section-3.2), for
+/// extended parameters such as `filename*` of `Content-Disposition` and
+/// `title*` of `Link`.
+///
+/// These work with `String` values, converting from and to the bytes of the
+/// given charset. See `parse_extended_value` and `ExtendedValue` for access
+/// to the raw bytes and language tag.
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::Charset;
+/// use hyperx::header::parsing::ext_value;
+///
+/// let utf8 = Charset::Ext("UTF-8".to_owned());
+/// assert_eq!(ext_value::encode(utf8.clone(), "€ rates").unwrap(), "UTF-8''%E2%82%AC%20rates");
+/// assert_eq!(ext_value::decode("UTF-8''%e2%82%ac").unwrap(), (utf8, "€".to_owned()));
+/// ```
+pub mod ext_value {
+    use header::shared::Charset;
+    use super::{ExtendedValue, parse_extended_value};
+
+    /// Encode a value as an `ext-value` of the given charset, without a
+    /// language tag.
+    ///
+    /// Only `UTF-8` and `ISO-8859-1` values can be encoded, as by `decode`.
+    /// Any other charset, or a character outside of Latin-1 for
+    /// `ISO-8859-1`, results in an error. Recipients are only required to
+    /// support `UTF-8`, which should be used where possible.
+    pub fn encode(charset: Charset, value: &str) -> ::Result<String> {
+        let value = if charset == Charset::Iso_8859_1 {
+            let mut bytes = Vec::with_capacity(value.len());
+            for c in value.chars() {
+                if (c as u32) >= 0x100 {
+                    return Err(::Error::Header);
+                }
+                bytes.push(c as u8);
+            }
+            bytes
+        } else if is_utf8(&charset) {
+            value.as_bytes().to_vec()
+        } else {
+            return Err(::Error::Header);
+        };
+        Ok(ExtendedValue {
+            charset: charset,
+            language_tag: None,
+            value: value,
+        }.to_string())
+    }
+
+    /// Decode an `ext-value` to its charset and value, ignoring any language
+    /// tag.
+    ///
+    /// Only `UTF-8` and `ISO-8859-1` values can be decoded. Any other
+    /// charset, or bytes which are not valid UTF-8, result in an error.
+    pub fn decode(s: &str) -> ::Result<(Charset, String)> {
+        let ext = try!(parse_extended_value(s));
+        let value = if ext.charset == Charset::Iso_8859_1 {
+            ext.value.iter().map(|&b| b as char).collect()
+        } else if is_utf8(&ext.charset) {
+            try!(String::from_utf8(ext.value))
+        } else {
+            return Err(::Error::Header);
+        };
+        Ok((ext.charset, value))
+    }
+
+    fn is_utf8(charset: &Charset) -> bool {
+        charset.to_string().eq_ignore_ascii_case("UTF-8")
+    }
+}
+
 /// Percent encode a sequence of bytes with a character set defined in
 /// [https://tools.ietf.org/html/rfc5987#section-3.2][url]
 ///
@@ -179,10 +523,185 @@ mod percent_encoding_http {
 
 #[cfg(test)]
 mod tests {
-    use header::shared::Charset;
-    use super::{ExtendedValue, parse_extended_value};
+    use header::Raw;
+    use header::shared::{Charset, QualityItem, q, qitem};
+    use std::fmt;
+    use std::time::Duration;
+    use std::{u32, u64};
+    use super::{ExtendedValue, comma_delimited_iter, ext_value, decode_byte_sequence, duration_to_secs_saturating,
+                encode_byte_sequence, fmt_token_or_quoted, from_comma_delimited, parse_extended_value,
+                is_token, split_unquoted, unescape};
     use language_tags::LanguageTag;
 
+    #[cfg(feature = "nightly")]
+    use test::Bencher;
+
+    #[test]
+    fn test_from_comma_delimited() {
+        let raw: Raw = vec![b" 1, ,2,\t".to_vec(), b"x, 3".to_vec()].into();
+        let r: Vec<u32> = from_comma_delimited(&raw).unwrap();
+        assert_eq!(r, vec![1, 2, 3]);
+
+        let raw: Raw = r#"a="b, c", "d\", e", f"#.into();
+        let r: Vec<String> = from_comma_delimited(&raw).unwrap();
+        assert_eq!(r, vec![r#"a="b, c""#, r#""d\", e""#, "f"]);
+    }
+
+    #[test]
+    fn test_byte_sequence_roundtrip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for len in 0..8 {
+            let encoded = encode_byte_sequence(&bytes[..len]);
+            assert_eq!(decode_byte_sequence(&encoded).unwrap(), &bytes[..len]);
+        }
+        let encoded = encode_byte_sequence(&bytes);
+        assert_eq!(decode_byte_sequence(&encoded).unwrap(), bytes);
+
+        assert_eq!(encode_byte_sequence(b""), "::");
+        assert_eq!(decode_byte_sequence(":cHJldGVuZCB0aGlzIGlzIGJpbmFyeSBjb250ZW50Lg==:").unwrap(),
+                   b"pretend this is binary content.");
+        // missing padding
+        assert_eq!(decode_byte_sequence(":aGVsbG8:").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_byte_sequence_malformed() {
+        assert!(decode_byte_sequence("").is_err());
+        assert!(decode_byte_sequence(":").is_err());
+        assert!(decode_byte_sequence("aGVsbG8=").is_err());
+        assert!(decode_byte_sequence(":aGVsbG8=").is_err());
+        assert!(decode_byte_sequence(" :aGVsbG8=:").is_err());
+        assert!(decode_byte_sequence(":aGVs bG8=:").is_err());
+        assert!(decode_byte_sequence(":aGVsbG8_:").is_err());
+        assert!(decode_byte_sequence(":aG=VsbG8:").is_err());
+        assert!(decode_byte_sequence(":aGVsbG8===:").is_err());
+        assert!(decode_byte_sequence(":a:").is_err());
+    }
+
+    #[test]
+    fn test_duration_to_secs_saturating() {
+        assert_eq!(duration_to_secs_saturating(Duration::from_secs(0)), 0);
+        assert_eq!(duration_to_secs_saturating(Duration::from_millis(499)), 0);
+        assert_eq!(duration_to_secs_saturating(Duration::from_millis(1500)), 2);
+        assert_eq!(duration_to_secs_saturating(Duration::new(59, 999_999_999)), 60);
+        assert_eq!(duration_to_secs_saturating(Duration::from_secs(u64::from(u32::MAX))), u32::MAX);
+        assert_eq!(duration_to_secs_saturating(Duration::new(u64::MAX, 999_999_999)), u32::MAX);
+    }
+
+    #[test]
+    fn test_duration_conversions() {
+        use header::{Age, CacheDirective, KeepAlive, RetryAfter, StrictTransportSecurity};
+
+        let cases = [
+            (Duration::from_millis(90_600), 91),
+            (Duration::new(u64::MAX, 0), u32::MAX),
+        ];
+        for &(duration, secs) in &cases {
+            assert_eq!(Age::from(duration), Age(secs));
+            assert_eq!(CacheDirective::max_age(duration), CacheDirective::MaxAge(secs));
+            assert_eq!(KeepAlive::from(duration).timeout, Some(secs));
+            assert_eq!(RetryAfter::from(duration), RetryAfter::Delay(Duration::from_secs(u64::from(secs))));
+            assert_eq!(StrictTransportSecurity::from(duration).max_age, u64::from(secs));
+        }
+    }
+
+    #[test]
+    fn test_comma_delimited_iter() {
+        let raw: Raw = vec![b" a, ,b,\t".to_vec(), b"".to_vec(), b"\"c, d\" ,e".to_vec()].into();
+        let items = comma_delimited_iter(&raw).map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(items, vec!["a", "b", "\"c, d\"", "e"]);
+
+        let raw: Raw = b"a, \xff, b".to_vec().into();
+        let mut iter = comma_delimited_iter(&raw);
+        assert_eq!(iter.next().unwrap().unwrap(), "a");
+        assert!(iter.next().unwrap().is_err());
+        assert_eq!(iter.next().unwrap().unwrap(), "b");
+        assert!(iter.next().is_none());
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_connection_eager(b: &mut Bencher) {
+        use header::ConnectionOption;
+        let raw: Raw = "keep-alive, te, x-trace, x-forwarded-for, upgrade, close".into();
+        b.iter(|| {
+            let opts: Vec<ConnectionOption> = from_comma_delimited(&raw).unwrap();
+            opts.contains(&ConnectionOption::Close)
+        });
+    }
+
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn bench_connection_lazy(b: &mut Bencher) {
+        let raw: Raw = "keep-alive, te, x-trace, x-forwarded-for, upgrade, close".into();
+        b.iter(|| {
+            comma_delimited_iter(&raw)
+                .any(|item| item.map(|s| s.eq_ignore_ascii_case("close")).unwrap_or(false))
+        });
+    }
+
+    #[test]
+    fn test_split_unquoted() {
+        let parts: Vec<_> = split_unquoted("a;b=\"x;\\\";y\";;c", b';').collect();
+        assert_eq!(parts, vec!["a", "b=\"x;\\\";y\"", "", "c"]);
+        assert_eq!(split_unquoted("", b';').collect::<Vec<_>>(), vec![""]);
+        assert_eq!(unescape("x;\\\";y"), "x;\";y");
+        assert!(is_token("permessage-deflate"));
+        assert!(!is_token("a b"));
+        assert!(!is_token(""));
+    }
+
+    #[test]
+    fn test_fmt_token_or_quoted() {
+        struct Value(&'static str);
+        impl fmt::Display for Value {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt_token_or_quoted(f, self.0)
+            }
+        }
+        assert_eq!(Value("no-cache").to_string(), "no-cache");
+        assert_eq!(Value("a b").to_string(), "\"a b\"");
+        assert_eq!(Value("").to_string(), "\"\"");
+        let quoted = Value("a\"b\\").to_string();
+        assert_eq!(quoted, "\"a\\\"b\\\\\"");
+        assert_eq!(unescape(&quoted[1..quoted.len() - 1]), "a\"b\\");
+    }
+
+    #[test]
+    fn test_from_comma_delimited_weighted() {
+        let raw: Raw = "gzip;q=0.5, br, identity; Q=0".into();
+        let r: Vec<QualityItem<String>> = from_comma_delimited(&raw).unwrap();
+        assert_eq!(r, vec![
+            QualityItem::new("gzip".to_owned(), q(500)),
+            qitem("br".to_owned()),
+            QualityItem::new("identity".to_owned(), q(0)),
+        ]);
+
+        let raw: Raw = vec![b"sha-256".to_vec(), b"md5;q=1.0".to_vec()].into();
+        let r: Vec<QualityItem<String>> = from_comma_delimited(&raw).unwrap();
+        assert_eq!(r, vec![qitem("sha-256".to_owned()), qitem("md5".to_owned())]);
+    }
+
+    #[test]
+    fn test_from_comma_delimited_weighted_malformed() {
+        let raw: Raw = "a;q=2, b;q=high, c;q=0.12345, d;q=, e;q=0.3".into();
+        let r: Vec<QualityItem<String>> = from_comma_delimited(&raw).unwrap();
+        assert_eq!(r, vec![QualityItem::new("e".to_owned(), q(300))]);
+    }
+
+    #[test]
+    fn test_from_comma_delimited_invalid_utf8() {
+        let raw: Raw = b"gzip, \xff\xfe, br".to_vec().into();
+        match from_comma_delimited::<_, String>(&raw) {
+            Err(::Error::Utf8(_)) => (),
+            other => panic!("expected Utf8 error, got {:?}", other),
+        }
+
+        // a multi-byte sequence truncated by a comma
+        let raw: Raw = "a, \u{e9}".as_bytes()[..4].to_vec().into();
+        assert!(from_comma_delimited::<_, String>(&raw).is_err());
+    }
+
     #[test]
     fn test_parse_extended_value_with_encoding_and_language_tag() {
         let expected_language_tag = "en".parse::<LanguageTag>().unwrap();
@@ -229,6 +748,32 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_ext_value_encode() {
+        let utf8 = Charset::Ext("UTF-8".to_owned());
+        let encoded = ext_value::encode(utf8.clone(), "£ and € rates.txt").unwrap();
+        assert_eq!(encoded, "UTF-8''%C2%A3%20and%20%E2%82%AC%20rates.txt");
+        assert_eq!(ext_value::decode(&encoded).unwrap(), (utf8, "£ and € rates.txt".to_owned()));
+
+        let encoded = ext_value::encode(Charset::Iso_8859_1, "£ rates").unwrap();
+        assert_eq!(encoded, "ISO-8859-1''%A3%20rates");
+        assert_eq!(ext_value::decode(&encoded).unwrap(),
+                   (Charset::Iso_8859_1, "£ rates".to_owned()));
+
+        assert!(ext_value::encode(Charset::Iso_8859_1, "€ rates").is_err());
+        assert!(ext_value::encode(Charset::Gb2312, "€").is_err());
+    }
+
+    #[test]
+    fn test_ext_value_decode() {
+        assert_eq!(ext_value::decode("UTF-8''%e2%82%ac").unwrap(),
+                   (Charset::Ext("UTF-8".to_owned()), "€".to_owned()));
+        assert_eq!(ext_value::decode("utf-8'en'%e2%82%ac").unwrap().1, "€");
+        assert!(ext_value::decode("UTF-8''%ff").is_err());
+        assert!(ext_value::decode("Shift_JIS''%82%a0").is_err());
+        assert!(ext_value::decode("%e2%82%ac").is_err());
+    }
+
     #[test]
     fn test_fmt_extended_value_with_encoding_and_language_tag() {
         let extended_value = ExtendedValue {
@@ -38,6 +38,25 @@ impl Raw {
         }
     }
 
+    /// Iterate the values (lines) as raw bytes.
+    ///
+    /// This is the same as `RawLike::iter`, but without needing the trait
+    /// in scope.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyperx::header::Raw;
+    ///
+    /// let raw: Raw = vec![b"gzip".to_vec(), b"br".to_vec()].into();
+    /// let lines = raw.iter().collect::<Vec<&[u8]>>();
+    /// assert_eq!(lines, vec![&b"gzip"[..], &b"br"[..]]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> RawLines {
+        RawLike::iter(self)
+    }
+
     fn push_line(&mut self, line: Bytes) {
         let lines = ::std::mem::replace(&mut self.0, Lines::Empty);
         match lines {
@@ -328,3 +347,24 @@ impl<'a> Iterator for RawLines<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Raw;
+
+    #[test]
+    fn test_iter() {
+        let raw: Raw = "one".into();
+        assert_eq!(raw.iter().collect::<Vec<_>>(), vec![&b"one"[..]]);
+
+        let mut raw: Raw = "one".into();
+        raw.push("two");
+        raw.push(b"three".to_vec());
+        assert_eq!(raw.iter().collect::<Vec<_>>(),
+                   vec![&b"one"[..], &b"two"[..], &b"three"[..]]);
+        assert_eq!((&raw).into_iter().count(), 3);
+
+        let raw: Raw = Vec::<Vec<u8>>::new().into();
+        assert_eq!(raw.iter().next(), None);
+    }
+}
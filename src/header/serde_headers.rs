@@ -0,0 +1,158 @@
+//! Implementation of the optional _serde_ support for `Headers`.
+
+use std::fmt;
+use std::str;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeMap, Serializer};
+
+use super::Headers;
+use super::parsing::is_token;
+
+/// Serializes as a map of header name to either a string, for a single
+/// value (line), or an array of strings, for multiple values.
+impl Serialize for Headers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = try!(serializer.serialize_map(Some(self.len())));
+        for header in self.iter() {
+            let mut values = Vec::new();
+            for line in header.raw() {
+                values.push(try!(str::from_utf8(line).map_err(ser::Error::custom)));
+            }
+            if values.len() == 1 {
+                try!(map.serialize_entry(header.name(), values[0]));
+            } else {
+                try!(map.serialize_entry(header.name(), &values));
+            }
+        }
+        map.end()
+    }
+}
+
+/// Deserializes from a map of header name to either a string or an array of
+/// strings, as produced by `Serialize`.
+///
+/// Names must be valid header field names (tokens). Values of names
+/// repeated in the map, in any case, are appended.
+impl<'de> Deserialize<'de> for Headers {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Headers, D::Error> {
+        deserializer.deserialize_map(HeadersVisitor)
+    }
+}
+
+struct HeadersVisitor;
+
+impl<'de> Visitor<'de> for HeadersVisitor {
+    type Value = Headers;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of header names to a string or an array of strings")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Headers, A::Error> {
+        let mut headers = Headers::with_capacity(access.size_hint().unwrap_or(0));
+        while let Some((name, values)) = try!(access.next_entry::<String, Values>()) {
+            if !is_token(&name) {
+                return Err(de::Error::invalid_value(
+                    de::Unexpected::Str(&name), &"a header field name"));
+            }
+            for value in values.0 {
+                headers.append_raw(name.clone(), value.into_bytes());
+            }
+        }
+        Ok(headers)
+    }
+}
+
+// One or more values (lines) of a header.
+struct Values(Vec<String>);
+
+impl<'de> Deserialize<'de> for Values {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Values, D::Error> {
+        deserializer.deserialize_any(ValuesVisitor)
+    }
+}
+
+struct ValuesVisitor;
+
+impl<'de> Visitor<'de> for ValuesVisitor {
+    type Value = Values;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string or a non-empty array of strings")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Values, E> {
+        Ok(Values(vec![value.to_owned()]))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Values, E> {
+        Ok(Values(vec![value]))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut access: A) -> Result<Values, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = try!(access.next_element()) {
+            values.push(value);
+        }
+        if values.is_empty() {
+            return Err(de::Error::invalid_length(0, &self));
+        }
+        Ok(Values(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use unicase::Ascii;
+
+    use header::{Connection, ConnectionOption, Headers};
+
+    #[test]
+    fn test_roundtrip() {
+        let mut headers = Headers::new();
+        headers.set_raw("Host", "example.com");
+        headers.append_raw("Connection", b"keep-alive".to_vec());
+        headers.append_raw("Connection", b"Upgrade".to_vec());
+
+        let json = serde_json::to_string(&headers).unwrap();
+        assert_eq!(json, r#"{"Host":"example.com","Connection":["keep-alive","Upgrade"]}"#);
+
+        let parsed: Headers = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, headers);
+        assert_eq!(parsed.get_raw("connection").unwrap().iter().count(), 2);
+        assert_eq!(parsed.get::<Connection>(), Some(&Connection(vec![
+            ConnectionOption::KeepAlive,
+            ConnectionOption::ConnectionHeader(Ascii::new("Upgrade".to_owned())),
+        ])));
+    }
+
+    #[test]
+    fn test_typed_value() {
+        let mut headers = Headers::new();
+        headers.set(Connection::close());
+        assert_eq!(serde_json::to_string(&headers).unwrap(), r#"{"Connection":"close"}"#);
+    }
+
+    #[test]
+    fn test_repeated_name() {
+        let headers: Headers =
+            serde_json::from_str(r#"{"accept":"text/html","Accept":["*/*"]}"#).unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers.get_raw("Accept").unwrap(), &["text/html", "*/*"][..]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(serde_json::from_str::<Headers>(r#"{"Bad Name":"x"}"#).is_err());
+        assert!(serde_json::from_str::<Headers>(r#"{"":"x"}"#).is_err());
+        assert!(serde_json::from_str::<Headers>(r#"{"X-Empty":[]}"#).is_err());
+        assert!(serde_json::from_str::<Headers>(r#"{"X-Num":1}"#).is_err());
+        assert!(serde_json::from_str::<Headers>(r#"["Host"]"#).is_err());
+
+        let mut headers = Headers::new();
+        headers.set_raw("X-Bin", vec![0xff]);
+        assert!(serde_json::to_string(&headers).is_err());
+    }
+}
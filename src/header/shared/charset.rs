@@ -12,7 +12,7 @@ use self::Charset::*;
 /// See [http://www.iana.org/assignments/character-sets/character-sets.xhtml][url].
 ///
 /// [url]: http://www.iana.org/assignments/character-sets/character-sets.xhtml
-#[derive(Clone,Debug,PartialEq)]
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
 #[allow(non_camel_case_types)]
 pub enum Charset{
     /// US ASCII
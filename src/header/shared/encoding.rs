@@ -1,11 +1,18 @@
 use std::fmt;
 use std::str;
 
-pub use self::Encoding::{Chunked, Brotli, Gzip, Deflate, Compress, Identity, EncodingExt, Trailers};
+use header::parsing::{fmt_token_or_quoted, is_token, split_unquoted, unescape};
+
+pub use self::Encoding::{Chunked, Brotli, Gzip, Deflate, Compress, Identity, EncodingExt, Trailers,
+                         DictionaryBrotli, DictionaryZstd};
 
 /// A value to represent an encoding used in `Transfer-Encoding`
 /// or `Accept-Encoding` header.
-#[derive(Clone, PartialEq, Debug)]
+///
+/// A coding which is not otherwise known, or which carries parameters other
+/// than those of `dcb` and `dcz`, is kept whole as `EncodingExt` so that it
+/// formats back unchanged.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
 pub enum Encoding {
     /// The `chunked` encoding.
     Chunked,
@@ -21,13 +28,55 @@ pub enum Encoding {
     Identity,
     /// The `trailers` encoding.
     Trailers,
+    /// The `dcb` dictionary-compressed Brotli encoding, with any parameters
+    /// as name and (unquoted) value pairs.
+    DictionaryBrotli(Vec<(String, String)>),
+    /// The `dcz` dictionary-compressed Zstandard encoding, with any
+    /// parameters as name and (unquoted) value pairs.
+    DictionaryZstd(Vec<(String, String)>),
     /// Some other encoding that is less common, can be any String.
     EncodingExt(String)
 }
 
+impl Encoding {
+    /// Whether this is valid as a content-coding, as listed by the
+    /// `Content-Encoding` and `Accept-Encoding` headers.
+    ///
+    /// This is all but `chunked` and `trailers`, which only apply to
+    /// transfer-codings.
+    pub fn is_valid_content_coding(&self) -> bool {
+        match *self {
+            Chunked | Trailers => false,
+            EncodingExt(ref s) => !is_any_of(s, &["chunked", "trailers"]),
+            _ => true,
+        }
+    }
+
+    /// Whether this is valid as a transfer-coding, as listed by the
+    /// `Transfer-Encoding` and `TE` headers.
+    ///
+    /// Of the known codings, only `chunked`, `compress`, `deflate` and `gzip`
+    /// are registered as transfer-codings, while `br`, `dcb`, `dcz` and
+    /// `identity` are content-codings only. `trailers` is not a coding,
+    /// though `TE` may list it. Any other coding is assumed to be valid.
+    pub fn is_valid_transfer_coding(&self) -> bool {
+        match *self {
+            Chunked | Compress | Deflate | Gzip => true,
+            EncodingExt(ref s) => !is_any_of(s, &["br", "dcb", "dcz", "identity", "trailers"]),
+            _ => false,
+        }
+    }
+}
+
+// Codings are case-insensitive, though only lowercase names parse as the
+// known variants.
+fn is_any_of(coding: &str, names: &[&str]) -> bool {
+    names.iter().any(|name| coding.eq_ignore_ascii_case(name))
+}
+
 impl fmt::Display for Encoding {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(match *self {
+        try!(f.write_str(match *self {
             Chunked => "chunked",
             Brotli => "br",
             Gzip => "gzip",
@@ -35,8 +84,20 @@ impl fmt::Display for Encoding {
             Compress => "compress",
             Identity => "identity",
             Trailers => "trailers",
+            DictionaryBrotli(_) => "dcb",
+            DictionaryZstd(_) => "dcz",
             EncodingExt(ref s) => s.as_ref()
-        })
+        }));
+        match *self {
+            DictionaryBrotli(ref params) | DictionaryZstd(ref params) => {
+                for (name, value) in params {
+                    try!(write!(f, ";{}=", name));
+                    try!(fmt_token_or_quoted(f, value));
+                }
+            },
+            _ => (),
+        }
+        Ok(())
     }
 }
 
@@ -51,7 +112,72 @@ impl str::FromStr for Encoding {
             "compress" => Ok(Compress),
             "identity" => Ok(Identity),
             "trailers" => Ok(Trailers),
-            _ => Ok(EncodingExt(s.to_owned()))
+            _ => Ok(parse_dictionary(s).unwrap_or_else(|| EncodingExt(s.to_owned())))
         }
     }
 }
+
+/// Parse `dcb` or `dcz` by the coding token before any parameters, or
+/// return `None` for other codings or malformed parameters.
+fn parse_dictionary(s: &str) -> Option<Encoding> {
+    let mut parts = split_unquoted(s, b';');
+    let coding = parts.next().unwrap_or("").trim();
+    if coding != "dcb" && coding != "dcz" {
+        return None;
+    }
+    let mut params = Vec::new();
+    for param in parts {
+        let mut pair = param.splitn(2, '=');
+        let name = pair.next().unwrap_or("").trim();
+        let value = match pair.next() {
+            Some(value) => value.trim(),
+            None => return None,
+        };
+        let value = if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+            unescape(&value[1..value.len() - 1])
+        } else if is_token(value) {
+            value.to_owned()
+        } else {
+            return None;
+        };
+        if !is_token(name) {
+            return None;
+        }
+        params.push((name.to_owned(), value));
+    }
+    Some(if coding == "dcb" {
+        DictionaryBrotli(params)
+    } else {
+        DictionaryZstd(params)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoding;
+    use super::Encoding::*;
+
+    #[test]
+    fn test_dictionary() {
+        assert_eq!("dcb".parse::<Encoding>().unwrap(), DictionaryBrotli(vec![]));
+        assert_eq!("dcz".parse::<Encoding>().unwrap(), DictionaryZstd(vec![]));
+
+        let dcb = "dcb; dict=\"a b\";x=1".parse::<Encoding>().unwrap();
+        assert_eq!(dcb, DictionaryBrotli(vec![
+            ("dict".to_owned(), "a b".to_owned()),
+            ("x".to_owned(), "1".to_owned()),
+        ]));
+        assert_eq!(dcb.to_string(), "dcb;dict=\"a b\";x=1");
+        assert_eq!("dcz;x=1".parse::<Encoding>().unwrap(),
+                   DictionaryZstd(vec![("x".to_owned(), "1".to_owned())]));
+    }
+
+    #[test]
+    fn test_ext() {
+        assert_eq!("dcb;x".parse::<Encoding>().unwrap(), EncodingExt("dcb;x".to_owned()));
+        assert_eq!("DCB".parse::<Encoding>().unwrap(), EncodingExt("DCB".to_owned()));
+        assert_eq!("gzip;level=3".parse::<Encoding>().unwrap(),
+                   EncodingExt("gzip;level=3".to_owned()));
+        assert_eq!("dcbx".parse::<Encoding>().unwrap(), EncodingExt("dcbx".to_owned()));
+    }
+}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::fmt::{self, Display};
 use std::str::FromStr;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -27,9 +28,38 @@ use time;
 //   header field that contains one or more timestamps defined as
 //   HTTP-date, the sender MUST generate those timestamps in the
 //   IMF-fixdate format.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+//
+// Dates are compared by the instant they represent, to the second, so that
+// the same time parsed from any of the three formats compares equal.
+#[derive(Clone, Copy, Debug)]
 pub struct HttpDate(time::Tm);
 
+impl HttpDate {
+    fn secs(&self) -> i64 {
+        self.0.to_timespec().sec
+    }
+}
+
+impl PartialEq for HttpDate {
+    fn eq(&self, other: &HttpDate) -> bool {
+        self.secs() == other.secs()
+    }
+}
+
+impl Eq for HttpDate {}
+
+impl PartialOrd for HttpDate {
+    fn partial_cmp(&self, other: &HttpDate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HttpDate {
+    fn cmp(&self, other: &HttpDate) -> Ordering {
+        self.secs().cmp(&other.secs())
+    }
+}
+
 impl FromStr for HttpDate {
     type Err = ::Error;
     fn from_str(s: &str) -> ::Result<HttpDate> {
@@ -78,6 +108,8 @@ impl From<HttpDate> for SystemTime {
 
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
     use time::Tm;
     use super::HttpDate;
 
@@ -110,6 +142,31 @@ mod tests {
         assert_eq!("Sun Nov  7 08:48:37 1994".parse::<HttpDate>().unwrap(), NOV_07);
     }
 
+    #[test]
+    fn test_cmp() {
+        let imf = "Sun, 07 Nov 1994 08:48:37 GMT".parse::<HttpDate>().unwrap();
+        let rfc_850 = "Sunday, 07-Nov-94 08:48:37 GMT".parse::<HttpDate>().unwrap();
+        let asctime = "Sun Nov  7 08:48:37 1994".parse::<HttpDate>().unwrap();
+        assert_eq!(imf, rfc_850);
+        assert_eq!(rfc_850, asctime);
+        assert_eq!(imf.cmp(&asctime), Ordering::Equal);
+
+        let later = "Sun, 07 Nov 1994 08:48:38 GMT".parse::<HttpDate>().unwrap();
+        assert!(imf < later);
+        assert!(later > asctime);
+        assert!(NOV_07 <= imf && NOV_07 >= rfc_850);
+    }
+
+    #[test]
+    fn test_cmp_second_granularity() {
+        let time = UNIX_EPOCH + Duration::new(784198117, 0);
+        let date = HttpDate::from(time);
+        assert_eq!(date, NOV_07);
+        assert_eq!(HttpDate::from(time + Duration::from_millis(999)), date);
+        assert!(HttpDate::from(time + Duration::from_secs(1)) > date);
+        assert_eq!(SystemTime::from(NOV_07), time);
+    }
+
     #[test]
     fn test_no_date() {
         assert!("this-is-no-date".parse::<HttpDate>().is_err());
@@ -0,0 +1,131 @@
+use std::fmt;
+use std::str;
+
+use mime::{self, Mime};
+
+/// A media range, as used in the `Accept` header, defined in
+/// [RFC7231](https://tools.ietf.org/html/rfc7231#section-5.3.2).
+///
+/// A media range is a media type in which the subtype, or both the type and
+/// subtype, may be the wildcard `*`, optionally with parameters that a
+/// matching media type must also have.
+///
+/// # ABNF
+///
+/// ```text
+/// media-range = ( "*/*"
+///               / ( type "/" "*" )
+///               / ( type "/" subtype )
+///               ) *( OWS ";" OWS parameter )
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use hyperx::header::MediaRange;
+///
+/// let range: MediaRange = "text/*".parse().unwrap();
+/// assert!(range.matches(&"text/html".parse().unwrap()));
+/// assert!(!range.matches(&"image/png".parse().unwrap()));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct MediaRange(Mime);
+
+impl MediaRange {
+    /// Create a `MediaRange` from a `Mime`, or `None` if it has a wildcard
+    /// type with a specific subtype, such as `*/html`.
+    pub fn new(mime: Mime) -> Option<MediaRange> {
+        if mime.type_() == mime::STAR && mime.subtype() != mime::STAR {
+            None
+        } else {
+            Some(MediaRange(mime))
+        }
+    }
+
+    /// The `*/*` media range, matching any media type.
+    pub fn star_star() -> MediaRange {
+        MediaRange(mime::STAR_STAR)
+    }
+
+    /// Get the range as a `Mime`.
+    pub fn as_mime(&self) -> &Mime {
+        &self.0
+    }
+
+    /// Check whether a media type falls within this range.
+    ///
+    /// `*/*` matches any media type and `type/*` any subtype of `type`.
+    /// Otherwise the type and subtype must be equal. Any parameter of the
+    /// range, other than the `q` weight, must also be present with the same
+    /// value on `mime`.
+    pub fn matches(&self, mime: &Mime) -> bool {
+        let range = &self.0;
+        if range.type_() != mime::STAR {
+            if range.type_() != mime.type_() {
+                return false;
+            }
+            if range.subtype() != mime::STAR && range.subtype() != mime.subtype() {
+                return false;
+            }
+        }
+        range.params()
+            .filter(|&(name, _)| name != "q")
+            .all(|(name, value)| mime.get_param(name) == Some(value))
+    }
+}
+
+impl str::FromStr for MediaRange {
+    type Err = ::Error;
+    fn from_str(s: &str) -> ::Result<MediaRange> {
+        s.parse().ok().and_then(MediaRange::new).ok_or(::Error::Header)
+    }
+}
+
+impl fmt::Display for MediaRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MediaRange;
+    use mime::{self, Mime};
+
+    fn mime(s: &str) -> Mime {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_matches_subtype_wildcard() {
+        let range: MediaRange = "text/*".parse().unwrap();
+        assert!(range.matches(&mime::TEXT_HTML));
+        assert!(range.matches(&mime("text/plain; charset=utf-8")));
+        assert!(!range.matches(&mime::IMAGE_PNG));
+    }
+
+    #[test]
+    fn test_matches_star_star() {
+        let range = MediaRange::star_star();
+        assert!(range.matches(&mime::TEXT_HTML));
+        assert!(range.matches(&mime::IMAGE_PNG));
+        assert_eq!(range, "*/*".parse().unwrap());
+    }
+
+    #[test]
+    fn test_matches_exact() {
+        let range: MediaRange = "Text/HTML".parse().unwrap();
+        assert!(range.matches(&mime::TEXT_HTML));
+        assert!(!range.matches(&mime::TEXT_PLAIN));
+
+        let range: MediaRange = "text/plain; format=flowed".parse().unwrap();
+        assert!(range.matches(&mime("text/plain; format=flowed; charset=utf-8")));
+        assert!(!range.matches(&mime::TEXT_PLAIN));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!("*/html".parse::<MediaRange>().is_err());
+        assert!("text".parse::<MediaRange>().is_err());
+    }
+}
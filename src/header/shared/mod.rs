@@ -3,10 +3,12 @@ pub use self::encoding::Encoding;
 pub use self::entity::EntityTag;
 pub use self::httpdate::HttpDate;
 pub use language_tags::LanguageTag;
+pub use self::media_range::MediaRange;
 pub use self::quality_item::{Quality, QualityItem, qitem, q};
 
 mod charset;
 mod encoding;
 mod entity;
 mod httpdate;
+mod media_range;
 mod quality_item;
@@ -23,9 +23,13 @@ extern crate language_tags;
 #[macro_use] extern crate log;
 pub extern crate mime;
 #[macro_use] extern crate percent_encoding;
+#[cfg(feature = "serde")]
+extern crate serde;
 extern crate time;
 extern crate unicase;
 
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 #[cfg(all(test, feature = "nightly"))]
 extern crate test;
 
@@ -36,5 +40,8 @@ pub use method::Method;
 mod error;
 mod method;
 mod common;
+#[cfg(test)]
+#[macro_use]
+mod test_support;
 pub mod header;
 
@@ -0,0 +1,129 @@
+//! Helpers for the crate's own tests.
+
+use std::fmt::{Debug, Write};
+use std::str;
+
+use header::{Header, Headers, Raw};
+
+/// Assert that two `Headers` are equal, panicking with a line-by-line diff of
+/// the header fields if they are not.
+macro_rules! assert_headers_eq {
+    ($actual:expr, $expected:expr) => {
+        if let Some(diff) = $crate::test_support::headers_diff(&$actual, &$expected) {
+            panic!("assertion failed: `(actual == expected)`\n{}", diff);
+        }
+    };
+}
+
+/// Describe the difference between two `Headers`, or `None` if they are
+/// equal.
+///
+/// Fields are listed in case-insensitive name order, one line per value.
+/// Fields with equal values are prefixed with a space, while differing
+/// fields are shown with `-` for the expected and `+` for the actual values.
+pub fn headers_diff(actual: &Headers, expected: &Headers) -> Option<String> {
+    if actual == expected {
+        return None;
+    }
+
+    let mut names = actual.iter().chain(expected.iter())
+        .map(|h| h.name())
+        .collect::<Vec<&str>>();
+    names.sort_by_key(|name| name.to_ascii_lowercase());
+    names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+
+    let mut diff = String::from("headers differ (-expected +actual):\n");
+    for name in names {
+        let actual = values(actual, name);
+        let expected = values(expected, name);
+        if actual == expected {
+            for value in &actual {
+                writeln!(diff, "  {}: {}", name, value).unwrap();
+            }
+        } else {
+            for value in &expected {
+                writeln!(diff, "- {}: {}", name, value).unwrap();
+            }
+            for value in &actual {
+                writeln!(diff, "+ {}: {}", name, value).unwrap();
+            }
+        }
+    }
+    Some(diff)
+}
+
+fn values(headers: &Headers, name: &str) -> Vec<String> {
+    headers.get_raw(name).map_or_else(Vec::new, |raw| {
+        raw.iter().map(|line| String::from_utf8_lossy(line).into_owned()).collect()
+    })
+}
+
+/// Parse `wire` as header `H`, format it, parse the formatted value again
+/// and assert that both parsed values are equal, catching lossy
+/// parse/format pairs.
+pub fn assert_roundtrip<H: Header + PartialEq + Debug>(wire: &[u8]) {
+    let raw: Raw = wire.into();
+    let parsed = match H::parse_header(&raw) {
+        Ok(h) => h,
+        Err(e) => panic!("failed to parse {:?}: {:?}", str::from_utf8(wire), e),
+    };
+    let formatted: Raw = parsed.to_value_strings().into_iter()
+        .map(String::into_bytes)
+        .collect::<Vec<_>>()
+        .into();
+    match H::parse_header(&formatted) {
+        Ok(reparsed) => assert_eq!(parsed, reparsed, "formatted as {:?}", formatted),
+        Err(e) => panic!("failed to re-parse {:?} (from {:?}): {:?}",
+                         formatted, str::from_utf8(wire), e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use header::{Headers, ContentLength, Host};
+    use super::headers_diff;
+
+    fn headers() -> Headers {
+        let mut headers = Headers::new();
+        headers.set(Host::new("example.com", None));
+        headers.set(ContentLength(10));
+        headers
+    }
+
+    #[test]
+    fn test_equal() {
+        let mut expected = Headers::new();
+        expected.set_raw("content-length", "10");
+        expected.set_raw("host", "example.com");
+        assert_eq!(headers_diff(&headers(), &expected), None);
+        assert_headers_eq!(headers(), expected);
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut actual = headers();
+        actual.append_raw("X-Trace", "a");
+        actual.append_raw("X-Trace", "b");
+        let mut expected = headers();
+        expected.set(ContentLength(12));
+        expected.set_raw("Accept", "*/*");
+
+        assert_eq!(headers_diff(&actual, &expected).unwrap(), "\
+headers differ (-expected +actual):
+- Accept: */*
+- Content-Length: 12
++ Content-Length: 10
+  Host: example.com
++ X-Trace: a
++ X-Trace: b
+");
+    }
+
+    #[test]
+    #[should_panic(expected = "- Content-Length: 12\n+ Content-Length: 10\n")]
+    fn test_assert_headers_eq_mismatch() {
+        let mut expected = headers();
+        expected.set(ContentLength(12));
+        assert_headers_eq!(headers(), expected);
+    }
+}